@@ -0,0 +1,57 @@
+//! Validates the bundled word list and embeds it grouped by length,
+//! so an invalid bundled word is a build error and loading it needs no allocation.
+
+use std::{
+    collections::BTreeMap,
+    env,
+    fs,
+    ops::RangeInclusive,
+    path::Path
+};
+
+
+
+const WORD_LIST: &str = "res/word_list.txt";
+/// Same as `WORD_RANGE` in `src/words.rs`
+const WORD_RANGE: RangeInclusive<usize> = 4..=6;
+
+
+fn main() {
+    println!("cargo:rerun-if-changed={WORD_LIST}");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let content = fs::read_to_string(WORD_LIST)
+        .unwrap_or_else(|e| panic!("Cannot read {WORD_LIST}: {e}"));
+
+    let mut groups: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    let mut invalid: Vec<String> = Vec::new();
+    for (pos, word) in content.lines().enumerate() {
+        let word = word.trim_end();
+        if !WORD_RANGE.contains(&word.len()) || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+            invalid.push(format!("- '{word}' at index {pos}"));
+        }
+        else {
+            groups.entry(word.len()).or_default().push(word);
+        }
+    }
+    if !invalid.is_empty() {
+        panic!(
+            "{WORD_LIST} should only have lowercase ASCII words of length {} to {}:\n{}",
+            WORD_RANGE.start(),
+            WORD_RANGE.end(),
+            invalid.join("\n")
+        );
+    }
+
+    let mut generated = String::from("/// Bundled words grouped by length, each group is its sorted words written one after another\n");
+    generated += "pub(crate) static BUNDLED_WORDS: &[(usize, &str)] = &[\n";
+    for (length, mut words) in groups {
+        words.sort_unstable();
+        words.dedup();
+        generated += &format!("    ({length}, \"{}\"),\n", words.concat());
+    }
+    generated += "];\n";
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("word_list.rs");
+    fs::write(out, generated).unwrap();
+}
@@ -0,0 +1,29 @@
+//! Plays arbitrary lines as guesses, the first one being the answer.
+//!
+//! `cargo +nightly fuzz run guess`
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use word_game::wordle::{GameConfig, WordleGame};
+
+fuzz_target!(|input: &str| {
+    let lines: Vec<&str> = input.lines().collect();
+    let Some((answer, guesses)) = lines.split_first() else {
+        return;
+    };
+    let words = lines.iter().map(|line| line.to_string()).collect::<Vec<_>>().into();
+    let config = GameConfig { attempts: Some(guesses.len()), hard: guesses.len() % 2 == 0 };
+    let Ok(mut game) = WordleGame::new_with_answer(words, answer).and_then(|game| game.with_config(config)) else {
+        return;
+    };
+
+    for guess in guesses {
+        if let Ok(pattern) = game.guess(guess) {
+            assert_eq!(pattern.len(), game.answer().chars().count());
+            assert!(game.knowledge().matches(game.answer()));
+        }
+        let _ = game.hint();
+    }
+    assert!(game.lives() <= guesses.len());
+});
@@ -0,0 +1,28 @@
+//! Parses arbitrary bytes as a word list in every format, then validates its lines.
+//!
+//! `cargo +nightly fuzz run word_list`
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use word_game::words::{self, WordListFormat};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    // Like a list without a known extension, whose format is detected from its content
+    let _ = WordListFormat::detect("", content);
+    for format in [WordListFormat::Text, WordListFormat::Json] {
+        let _ = words::parse(content, format);
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let valid = match words::validate_list(&lines) {
+        Ok(valid) | Err((valid, _)) => valid
+    };
+    for word in valid {
+        assert_eq!(words::validate_word(&word).ok(), Some(word));
+    }
+});
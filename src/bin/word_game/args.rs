@@ -0,0 +1,255 @@
+use clap::{
+    Args as ClapArgs,
+    Parser,
+    Subcommand,
+    ValueEnum
+};
+use clap_complete::Shell;
+use std::path::{Path, PathBuf};
+
+use crate::ui::KeyboardLayout;
+use word_game::wordle::Difficulty;
+
+
+
+/// Play wordle in terminal
+#[derive(Debug, Parser)]
+#[command(
+    name = "word_game",
+    version,
+    args_conflicts_with_subcommands = true,
+    after_help = WORD_LIST_HELP
+)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    play: PlayArgs,
+    /// Word lists to play with, like --words, as given before there were subcommands
+    #[arg(value_name = "WORD_LIST", hide = true)]
+    word_lists: Vec<String>,
+    /// Config file with default settings, $XDG_CONFIG_HOME/word_game/config.toml by default
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>
+}
+
+const WORD_LIST_HELP: &str = "\
+Word lists contain the words each written on new line.
+Lines starting with '#' are comments, blank lines are skipped.
+Leading '#! key: value' lines set the name, language and length of the list.
+A word can be followed by a tab and its frequency.
+JSON arrays (.json) and gzip-compressed lists (.gz) are also accepted.
+
+The config file sets defaults for the word lists (words, exclude, answers),
+length, attempts, hard, theme, layout, animations and the [keys] submit, delete, clear,
+quit, hint, undo, next, previous and theme bindings. Command line options take precedence.
+
+Theme files are TOML files setting text, border, error and the correct, present,
+wrong and unknown tiles to colours like \"white on green\", \"#538d4e\" or \"on 236\".
+Missing keys keep the colours of the classic theme.";
+
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Play a game with a random answer (default)
+    Play(PlayArgs),
+    /// Play the word of the day, the same for everyone with the same word lists
+    Daily(PlayArgs),
+    /// List the words that match the feedback of previous guesses
+    Solve(SolveArgs),
+    /// Print statistics about word lists
+    Stats(StatsArgs),
+    /// Report issues in a word list
+    Lint(LintArgs),
+    /// Time loading the bundled word list and validating guesses
+    Bench(WordsArgs),
+    /// Print the effective settings and where each one comes from
+    Config(PlayArgs),
+    /// Step through a game saved with --save
+    Replay(ReplayArgs),
+    /// Print a shell completion script
+    Completions {
+        shell: Shell
+    }
+}
+
+
+#[derive(Debug, Clone, Default, ClapArgs)]
+pub struct WordsArgs {
+    /// Word lists to use instead of the default one, merged in order.
+    /// Frequencies and header fields of later lists take precedence
+    #[arg(short, long = "words", value_name = "WORD_LIST")]
+    pub words: Vec<String>,
+    /// Word lists of words to remove from the dictionary
+    #[arg(short = 'x', long = "exclude", value_name = "EXCLUDE")]
+    pub excludes: Vec<String>,
+    /// Only use words of this length
+    #[arg(short, long)]
+    pub length: Option<usize>
+}
+
+
+#[derive(Debug, Clone, Default, ClapArgs)]
+pub struct BlocklistArgs {
+    /// Word list of words that are never picked as the answer, in addition to the default blocklist
+    #[arg(short, long)]
+    pub blocklist: Option<String>,
+    /// Do not use the bundled blocklist of offensive words
+    #[arg(long)]
+    pub no_default_blocklist: bool,
+    /// Also refuse words from the blocklists as guesses
+    #[arg(long)]
+    pub block_guesses: bool
+}
+
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum DifficultyLevel {
+    /// Prefer common words
+    Easy,
+    /// Ignore frequencies
+    #[default]
+    Normal,
+    /// Prefer rare words
+    Hard
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Protocol {
+    /// One JSON command per line on standard input, one JSON response per line on standard output
+    Json
+}
+
+#[derive(Debug, Clone, Default, ClapArgs)]
+pub struct PlayArgs {
+    #[command(flatten)]
+    pub words: WordsArgs,
+    /// Word lists the answer is picked from instead of all the words
+    #[arg(short, long = "answers", value_name = "ANSWERS")]
+    pub answers: Vec<String>,
+    /// Number of guesses, the length of the answer by default
+    #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub attempts: Option<usize>,
+    /// Guesses must use every revealed hint
+    #[arg(long, overrides_with = "no_hard")]
+    pub hard: bool,
+    /// Allow guesses ignoring hints, even if the config file enables hard mode
+    #[arg(long, overrides_with = "hard")]
+    pub no_hard: bool,
+    /// Seed of the answer picking, the same seed and word lists give the same answer
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// How the answer is picked using the word frequencies from the word lists
+    #[arg(short, long, value_enum, default_value_t)]
+    pub difficulty: DifficultyLevel,
+    /// Pick the answer only among the COUNT most common words
+    #[arg(short, long, value_name = "COUNT", conflicts_with = "difficulty")]
+    pub top: Option<usize>,
+    #[command(flatten)]
+    pub blocklists: BlocklistArgs,
+    /// Colours of the interface: classic, dark, light, high-contrast, monochrome or the path of a theme file
+    #[arg(long, value_name = "THEME")]
+    pub theme: Option<String>,
+    /// Order of the letters on the on-screen keyboard
+    #[arg(long, value_enum)]
+    pub layout: Option<KeyboardLayout>,
+    /// Show the tiles without flipping, shaking or bouncing them, for slow terminals
+    #[arg(long)]
+    pub no_animations: bool,
+    /// Play line by line on standard input and output instead of the full screen interface,
+    /// for dumb terminals, screen readers and scripts
+    #[arg(long)]
+    pub plain: bool,
+    /// Play through a machine-readable protocol instead, for bots and other front-ends
+    #[arg(long, value_enum, conflicts_with = "plain")]
+    pub protocol: Option<Protocol>,
+    /// Record the game in this replay file, an unfinished game saved there is resumed
+    #[arg(long, value_name = "FILE", conflicts_with = "protocol")]
+    pub save: Option<String>
+}
+
+impl PlayArgs {
+    pub fn difficulty(&self) -> Difficulty {
+        match (self.top, self.difficulty) {
+            (Some(count), _) => Difficulty::Top(count),
+            (None, DifficultyLevel::Easy) => Difficulty::Easy,
+            (None, DifficultyLevel::Normal) => Difficulty::Normal,
+            (None, DifficultyLevel::Hard) => Difficulty::Hard
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, ClapArgs)]
+pub struct SolveArgs {
+    #[command(flatten)]
+    pub words: WordsArgs,
+    /// Previous guesses with their feedback, like 'crane=..y.g'.
+    /// 'g' is a correct letter, 'y' is a present letter, anything else is a wrong letter
+    #[arg(value_name = "GUESS=FEEDBACK")]
+    pub guesses: Vec<String>,
+    /// Maximum number of matching words to print
+    #[arg(short = 'n', long, default_value_t = 20)]
+    pub limit: usize
+}
+
+
+#[derive(Debug, Clone, ClapArgs)]
+pub struct ReplayArgs {
+    #[command(flatten)]
+    pub words: WordsArgs,
+    /// The blocklists the game was played with, to remove the same words with --block-guesses
+    #[command(flatten)]
+    pub blocklists: BlocklistArgs,
+    /// Replay file written by 'play --save'
+    pub file: String
+}
+
+
+#[derive(Debug, Clone, ClapArgs)]
+pub struct StatsArgs {
+    #[command(flatten)]
+    pub words: WordsArgs,
+    /// Print the statistics as JSON instead of a table
+    #[arg(long)]
+    pub json: bool
+}
+
+
+#[derive(Debug, Clone, ClapArgs)]
+pub struct LintArgs {
+    /// Word list to check for duplicates, invalid characters, invalid lengths,
+    /// uppercase letters and non-normalized Unicode. Exits with 1 if there are issues
+    pub word_list: String,
+    /// Also lint this answer list and report answers missing from WORD_LIST
+    #[arg(short, long)]
+    pub answers: Option<String>,
+    /// Write the valid words normalized, sorted and deduplicated
+    #[arg(long)]
+    pub fix: bool,
+    /// Where to write the fixed list, WORD_LIST by default
+    #[arg(short, long, requires = "fix")]
+    pub output: Option<String>
+}
+
+
+
+impl Args {
+    pub fn new() -> Self {
+        Self::parse()
+    }
+
+    /// The subcommand to run, playing if there is none
+    pub fn subcommand(&self) -> Command {
+        self.command.clone().unwrap_or_else(|| {
+            let mut play = self.play.clone();
+            play.words.words.splice(0..0, self.word_lists.clone());
+            Command::Play(play)
+        })
+    }
+
+    /// Config file given on the command line
+    pub fn config(&self) -> Option<&Path> {
+        self.config.as_deref()
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant}
+};
+
+use word_game::dictionary::Dictionary;
+use word_game::words;
+
+
+
+/// Number of guesses looked up by every lookup method
+const LOOKUPS: usize = 2000;
+
+
+pub struct Measurement {
+    pub name: &'static str,
+    pub total: Duration,
+    pub count: usize
+}
+
+impl Measurement {
+    fn new<F>(name: &'static str, count: usize, mut f: F) -> Self
+        where F: FnMut()
+    {
+        let start = Instant::now();
+        f();
+        Self {
+            name,
+            total: start.elapsed(),
+            count
+        }
+    }
+
+    pub fn per_item(&self) -> Duration {
+        self.total / self.count.max(1) as u32
+    }
+}
+
+
+/// Compares guess validation with a linear scan over the words, as it was done before [`Dictionary`],
+/// with the dictionary lookup, and times loading the bundled word list from text and from the embedded groups.
+pub fn run(dictionary: &Dictionary) -> Vec<Measurement> {
+    let words: Vec<String> = dictionary.iter().map(str::to_string).collect();
+    let step = (words.len() / LOOKUPS).max(1);
+    // Every other guess is not a word, so misses that scan the whole list are measured too
+    let guesses: Vec<String> = words
+        .iter()
+        .step_by(step)
+        .enumerate()
+        .map(|(i, word)| if i % 2 == 0 { word.clone() } else { word.replace(|_| true, "q") })
+        .collect();
+
+    vec![
+        Measurement::new("Parse bundled word list", 1, || {
+            black_box(Dictionary::from(
+                include_str!("../../../res/word_list.txt").lines().map(str::to_owned).collect::<Vec<String>>()
+            ));
+        }),
+        Measurement::new("Load embedded word list", 1, || {
+            black_box(words::default_words());
+        }),
+        Measurement::new("Vec::contains lookup", guesses.len(), || {
+            for guess in &guesses {
+                black_box(words.contains(&guess.to_string()));
+            }
+        }),
+        Measurement::new("Dictionary::contains lookup", guesses.len(), || {
+            for guess in &guesses {
+                black_box(dictionary.contains(guess));
+            }
+        }),
+        Measurement::new("Embedded lookup", guesses.len(), || {
+            let bundled = Dictionary::bundled();
+            for guess in &guesses {
+                black_box(bundled.contains(guess));
+            }
+        })
+    ]
+}
+
+pub fn to_table(measurements: &[Measurement]) -> String {
+    let mut table = format!("{:<28}  {:>7}  {:>12}  {:>12}\n", "Benchmark", "Count", "Total", "Each");
+    for m in measurements {
+        table += &format!(
+            "{:<28}  {:>7}  {:>12}  {:>12}\n",
+            m.name,
+            m.count,
+            format!("{:.2?}", m.total),
+            format!("{:.2?}", m.per_item())
+        );
+    }
+    table
+}
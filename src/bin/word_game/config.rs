@@ -0,0 +1,299 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io,
+    path::{Path, PathBuf}
+};
+
+use serde::Deserialize;
+
+use crate::args::{PlayArgs, WordsArgs};
+use crate::theme::{Theme, ThemeError};
+use crate::ui::{KeyBinding, KeyBindings, KeyboardLayout, UiConfig};
+
+
+
+pub const DEFAULT_THEME: &str = "classic";
+
+
+/// Defaults read from the config file, every field is optional
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// File the config was read from, `None` if there was no file
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+    pub words: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+    pub answers: Option<Vec<String>>,
+    pub length: Option<usize>,
+    pub attempts: Option<usize>,
+    pub hard: Option<bool>,
+    pub theme: Option<String>,
+    pub layout: Option<KeyboardLayout>,
+    pub animations: Option<bool>,
+    pub keys: KeysConfig
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeysConfig {
+    pub submit: Option<KeyBinding>,
+    pub delete: Option<KeyBinding>,
+    pub clear: Option<KeyBinding>,
+    pub quit: Option<KeyBinding>,
+    pub hint: Option<KeyBinding>,
+    pub undo: Option<KeyBinding>,
+    pub next: Option<KeyBinding>,
+    pub previous: Option<KeyBinding>,
+    pub theme: Option<KeyBinding>
+}
+
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Unreadable {
+        reason: String
+    },
+    Invalid {
+        reason: String
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unreadable { reason } => write!(f, "Config file cannot be read: {reason}"),
+            Self::Invalid { reason } => write!(f, "Config file cannot be parsed: {reason}")
+        }
+    }
+}
+
+
+/// Where the effective value of a setting comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    ConfigFile,
+    CommandLine
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::ConfigFile => write!(f, "config file"),
+            Self::CommandLine => write!(f, "command line")
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source
+}
+
+impl<T> Setting<T> {
+    /// The command line value if given, then the config file value, then `default`
+    fn resolve(command_line: Option<T>, config_file: Option<T>, default: T) -> Self {
+        match (command_line, config_file) {
+            (Some(value), _) => Self { value, source: Source::CommandLine },
+            (None, Some(value)) => Self { value, source: Source::ConfigFile },
+            (None, None) => Self { value: default, source: Source::Default }
+        }
+    }
+}
+
+
+/// Effective settings of a game, the command line taking precedence over the config file
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub words: Setting<Vec<String>>,
+    pub exclude: Setting<Vec<String>>,
+    pub answers: Setting<Vec<String>>,
+    pub length: Setting<Option<usize>>,
+    pub attempts: Setting<Option<usize>>,
+    pub hard: Setting<bool>,
+    pub theme: Setting<String>,
+    pub layout: Setting<KeyboardLayout>,
+    pub animations: Setting<bool>,
+    pub submit: Setting<KeyBinding>,
+    pub delete: Setting<KeyBinding>,
+    pub clear: Setting<KeyBinding>,
+    pub quit: Setting<KeyBinding>,
+    pub hint: Setting<KeyBinding>,
+    pub undo: Setting<KeyBinding>,
+    pub next: Setting<KeyBinding>,
+    pub previous: Setting<KeyBinding>,
+    /// Key switching themes, `theme` being the theme itself
+    pub theme_key: Setting<KeyBinding>
+}
+
+
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/word_game/config.toml`, falling back to `~/.config`
+    pub fn default_path() -> Option<PathBuf> {
+        let config_home = env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+        Some(config_home.join("word_game").join("config.toml"))
+    }
+
+    /// Reads the config file at `path`, or at the default path if `None`.
+    /// A missing file at the default path is an empty config
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default())
+            }
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                let mut config = Self::parse(&content)?;
+                config.path = Some(path);
+                Ok(config)
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound && !required =>
+                Ok(Self::default()),
+            Err(error) =>
+                Err(ConfigError::Unreadable { reason: error.to_string() })
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        toml::from_str(content)
+            .map_err(|error| ConfigError::Invalid { reason: error.to_string() })
+    }
+
+    /// Word list arguments with the config file values where the command line has none
+    pub fn words_args(&self, args: &WordsArgs) -> WordsArgs {
+        WordsArgs {
+            words: non_empty(&args.words).or_else(|| self.words.clone()).unwrap_or_default(),
+            excludes: non_empty(&args.excludes).or_else(|| self.exclude.clone()).unwrap_or_default(),
+            length: args.length.or(self.length)
+        }
+    }
+
+    pub fn settings(&self, play: &PlayArgs) -> Settings {
+        let hard = match (play.hard, play.no_hard) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None
+        };
+        let keys = KeyBindings::default();
+
+        Settings {
+            words: Setting::resolve(non_empty(&play.words.words), self.words.clone(), Vec::new()),
+            exclude: Setting::resolve(non_empty(&play.words.excludes), self.exclude.clone(), Vec::new()),
+            answers: Setting::resolve(non_empty(&play.answers), self.answers.clone(), Vec::new()),
+            length: Setting::resolve(play.words.length.map(Some), self.length.map(Some), None),
+            attempts: Setting::resolve(play.attempts.map(Some), self.attempts.map(Some), None),
+            hard: Setting::resolve(hard, self.hard, false),
+            theme: Setting::resolve(play.theme.clone(), self.theme.clone(), DEFAULT_THEME.to_string()),
+            layout: Setting::resolve(play.layout, self.layout, KeyboardLayout::default()),
+            animations: Setting::resolve(play.no_animations.then_some(false), self.animations, true),
+            submit: Setting::resolve(None, self.keys.submit, keys.submit),
+            delete: Setting::resolve(None, self.keys.delete, keys.delete),
+            clear: Setting::resolve(None, self.keys.clear, keys.clear),
+            quit: Setting::resolve(None, self.keys.quit, keys.quit),
+            hint: Setting::resolve(None, self.keys.hint, keys.hint),
+            undo: Setting::resolve(None, self.keys.undo, keys.undo),
+            next: Setting::resolve(None, self.keys.next, keys.next),
+            previous: Setting::resolve(None, self.keys.previous, keys.previous),
+            theme_key: Setting::resolve(None, self.keys.theme, keys.theme)
+        }
+    }
+}
+
+
+impl Settings {
+    /// `play` with every setting replaced by its effective value
+    pub fn apply(&self, play: &PlayArgs) -> PlayArgs {
+        PlayArgs {
+            words: WordsArgs {
+                words: self.words.value.clone(),
+                excludes: self.exclude.value.clone(),
+                length: self.length.value
+            },
+            answers: self.answers.value.clone(),
+            attempts: self.attempts.value,
+            hard: self.hard.value,
+            no_hard: !self.hard.value,
+            theme: Some(self.theme.value.clone()),
+            layout: Some(self.layout.value),
+            no_animations: !self.animations.value,
+            ..play.clone()
+        }
+    }
+
+    /// Fails if the theme is neither built in nor a readable theme file
+    pub fn ui_config(&self) -> Result<UiConfig, ThemeError> {
+        Ok(UiConfig {
+            layout: self.layout.value,
+            theme: Theme::load(&self.theme.value)?,
+            animations: self.animations.value,
+            keys: KeyBindings {
+                submit: self.submit.value,
+                delete: self.delete.value,
+                clear: self.clear.value,
+                quit: self.quit.value,
+                hint: self.hint.value,
+                undo: self.undo.value,
+                next: self.next.value,
+                previous: self.previous.value,
+                theme: self.theme_key.value
+            }
+        })
+    }
+
+    pub fn to_table(&self) -> String {
+        let list = |paths: &[String]|
+            if paths.is_empty() { "none".to_string() } else { paths.join(", ") };
+        let optional = |value: Option<usize>|
+            value.map(|v| v.to_string()).unwrap_or_else(|| "any".to_string());
+
+        let rows = [
+            ("words", if self.words.value.is_empty() { "bundled".to_string() } else { list(&self.words.value) }, self.words.source),
+            ("exclude", list(&self.exclude.value), self.exclude.source),
+            ("answers", if self.answers.value.is_empty() { "words".to_string() } else { list(&self.answers.value) }, self.answers.source),
+            ("length", optional(self.length.value), self.length.source),
+            ("attempts", self.attempts.value.map(|v| v.to_string()).unwrap_or_else(|| "word length".to_string()), self.attempts.source),
+            ("hard", self.hard.value.to_string(), self.hard.source),
+            ("theme", self.theme.value.clone(), self.theme.source),
+            ("layout", self.layout.value.to_string(), self.layout.source),
+            ("animations", self.animations.value.to_string(), self.animations.source),
+            ("keys.submit", self.submit.value.to_string(), self.submit.source),
+            ("keys.delete", self.delete.value.to_string(), self.delete.source),
+            ("keys.clear", self.clear.value.to_string(), self.clear.source),
+            ("keys.quit", self.quit.value.to_string(), self.quit.source),
+            ("keys.hint", self.hint.value.to_string(), self.hint.source),
+            ("keys.undo", self.undo.value.to_string(), self.undo.source),
+            ("keys.next", self.next.value.to_string(), self.next.source),
+            ("keys.previous", self.previous.value.to_string(), self.previous.source),
+            ("keys.theme", self.theme_key.value.to_string(), self.theme_key.source)
+        ];
+        let width = rows.iter().map(|(_, value, _)| value.chars().count()).max().unwrap_or_default();
+
+        rows.iter()
+            .map(|(name, value, source)| format!("{name:<12} {value:<width$}  ({source})\n"))
+            .collect()
+    }
+}
+
+
+fn non_empty(values: &[String]) -> Option<Vec<String>> {
+    if values.is_empty() {
+        None
+    }
+    else {
+        Some(values.to_vec())
+    }
+}
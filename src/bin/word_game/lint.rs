@@ -0,0 +1,66 @@
+use std::fs;
+
+use colored::Colorize;
+
+use word_game::lint::{self, Finding};
+use word_game::words::{self, WordListFormat};
+
+
+
+/// Prints the issues of the word list at `path` and of the `answers` list,
+/// or writes the fixed word list to `fix_output`
+pub fn run(path: &str, answers: Option<&String>, fix_output: Option<&str>) -> Result<(), i32> {
+    let read = |path: &str| match words::read_entries(path) {
+        Ok(list) =>
+            Ok(list),
+        Err(error) => {
+            eprintln!("{}", format!("{path}: {error}").red());
+            Err(1)
+        }
+    };
+
+    let (header, entries) = read(path)?;
+    let mut clean = print_findings(path, &lint::lint(&entries));
+
+    if let Some(answers) = answers {
+        let (_, answer_entries) = read(answers)?;
+        clean &= print_findings(answers, &lint::lint(&answer_entries));
+        clean &= print_findings(answers, &lint::missing_answers(&entries, &answer_entries));
+    }
+
+    if let Some(output) = fix_output {
+        let bytes = fs::read(path).unwrap_or_default();
+        let content = String::from_utf8_lossy(&bytes);
+        if output == path && (words::is_compressed(path, &bytes) || WordListFormat::detect(path, &content) != WordListFormat::Text) {
+            eprintln!("{}", format!("{path}: only text word lists can be fixed in place, use --output").red());
+            return Err(1);
+        }
+        if fs::write(output, lint::fix(&header, &entries)).is_err() {
+            eprintln!("{}", format!("{output}: cannot write the fixed word list").red());
+            return Err(1);
+        }
+        println!("{}", format!("Wrote fixed word list to {output}").green());
+        return Ok(());
+    }
+
+    if clean {
+        Ok(())
+    }
+    else {
+        Err(1)
+    }
+}
+
+fn print_findings(path: &str, findings: &[Finding]) -> bool {
+    if findings.is_empty() {
+        println!("{}", format!("{path}: no issues").green());
+        true
+    }
+    else {
+        println!("{}", format!("{path}: {} issues", findings.len()).yellow());
+        for finding in findings {
+            println!("{finding}");
+        }
+        false
+    }
+}
@@ -0,0 +1,396 @@
+mod args;
+mod bench;
+mod config;
+mod lint;
+mod plain;
+mod solve;
+#[cfg(test)]
+mod tests;
+mod theme;
+mod ui;
+
+
+
+use std::{
+    env,
+    fs,
+    io::{self, IsTerminal, Read},
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH}
+};
+
+use clap::CommandFactory;
+use colored::Colorize;
+use crossterm::style::Stylize;
+use tui::{backend::CrosstermBackend};
+
+use word_game::{
+    dictionary::Dictionary,
+    history,
+    protocol,
+    stats,
+    wordle,
+    words
+};
+
+use args::{Args, BlocklistArgs, Command, PlayArgs, Protocol, WordsArgs};
+use config::{Config, Settings};
+use ui::{EventSource, Screen};
+
+
+
+/// Time between two frames of the animations, also the longest wait before noticing a signal
+const TICK: Duration = Duration::from_millis(40);
+
+
+
+fn main() -> Result<(), i32> {
+    let args = Args::new();
+
+    match args.subcommand() {
+        Command::Play(play) => {
+            let settings = load_config(args.config())?.settings(&play);
+            play_game(&settings.apply(&play), load_ui_config(&settings)?, play.seed)
+        },
+        Command::Daily(play) => {
+            // Same answer for the whole day
+            let day = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() / (24 * 60 * 60))
+                .unwrap_or_default();
+            let settings = load_config(args.config())?.settings(&play);
+            play_game(&settings.apply(&play), load_ui_config(&settings)?, Some(day))
+        },
+        Command::Solve(solve) => {
+            let words = load_words(&load_config(args.config())?.words_args(&solve.words), false)?.words;
+            solve::run(&words, &solve.guesses, solve.limit)
+        },
+        Command::Stats(stats) => {
+            let words = load_words(&load_config(args.config())?.words_args(&stats.words), false)?.words;
+            let stats_table = stats::Stats::new(words.iter());
+            if stats.json {
+                println!("{}", stats_table.to_json());
+            }
+            else {
+                print!("{}", stats_table.to_table());
+            }
+            Ok(())
+        },
+        Command::Lint(lint) => {
+            let output = if lint.fix { Some(lint.output.as_ref().unwrap_or(&lint.word_list).as_str()) } else { None };
+            lint::run(&lint.word_list, lint.answers.as_ref(), output)
+        },
+        Command::Bench(words) => {
+            let words = load_words(&load_config(args.config())?.words_args(&words), false)?.words;
+            print!("{}", bench::to_table(&bench::run(&words)));
+            Ok(())
+        },
+        Command::Config(play) => {
+            let config = load_config(args.config())?;
+            match (&config.path, Config::default_path()) {
+                (Some(path), _) => println!("Config file: {}", path.display()),
+                (None, Some(path)) => println!("Config file: {} (not found)", path.display()),
+                (None, None) => println!("Config file: none")
+            }
+            print!("{}", config.settings(&play).to_table());
+            Ok(())
+        },
+        Command::Replay(replay) => {
+            let config = load_config(args.config())?;
+            let words = load_words(&config.words_args(&replay.words), false)?.words;
+            let (words, _) = apply_blocklists(words, &replay.blocklists)?;
+            let game = history::read_from(&replay.file)
+                .and_then(|record| wordle::WordleGame::from_record(words, &record));
+            match game {
+                Ok(game) =>
+                    replay_game(&game, load_ui_config(&config.settings(&PlayArgs::default()))?),
+                Err(error) => {
+                    eprintln!("{}", format!("{}: {error}", replay.file).red());
+                    Err(1)
+                }
+            }
+        },
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Args::command(), "word_game", &mut io::stdout());
+            Ok(())
+        }
+    }
+}
+
+
+fn play_game(play: &PlayArgs, ui_config: ui::UiConfig, seed: Option<u64>) -> Result<(), i32> {
+    // Get words
+    // Waiting for ENTER would eat the first guess or command piped in
+    let confirm = !play.plain && play.protocol.is_none();
+    let list = load_words(&play.words, confirm)?;
+    if !list.normalization.is_empty() {
+        eprintln!("{}", list.normalization.to_string().yellow());
+    }
+
+    // Get answers
+    let answers =
+        if play.answers.is_empty() {
+            None
+        }
+        else {
+            let answers = load_words(
+                &WordsArgs {
+                    words: play.answers.clone(),
+                    ..play.words.clone()
+                },
+                confirm
+            )?.words;
+            let missing = answers.iter().filter(|a| !list.words.contains(a)).count();
+            if missing > 0 {
+                eprintln!("{}", format!("{missing} answers are not in the word lists and cannot be picked").yellow());
+            }
+            Some(answers.filter(|a| list.words.contains(a)))
+        };
+
+    // Get blocked answers
+    let (words, blocklist) = apply_blocklists(list.words, &play.blocklists)?;
+
+    let picker = wordle::AnswerPicker {
+        answers,
+        frequencies: list.frequencies,
+        blocklist,
+        difficulty: play.difficulty(),
+        seed
+    };
+    let config = wordle::GameConfig {
+        attempts: play.attempts,
+        hard: play.hard
+    };
+
+    if let Some(Protocol::Json) = play.protocol {
+        let mut session = protocol::Session::new(words, picker, config);
+        return protocol::run(&mut session, io::stdin().lock(), io::stdout()).map_err(|error| {
+            eprintln!("{}", error.to_string().red());
+            1
+        });
+    }
+
+    let game = match resume_game(play.save.as_deref(), &words)? {
+        Some(game) => Ok(game),
+        None => wordle::WordleGame::new(words, &picker).and_then(|game| game.with_config(config))
+    };
+    let mut game = match game {
+        Ok(game) => game,
+        Err(error) => {
+            eprintln!("{}", error.to_string().red());
+            return Err(1);
+        }
+    };
+
+    if play.plain {
+        // Colours would only garble the output of scripts
+        if !io::stdout().is_terminal() {
+            colored::control::set_override(false);
+        }
+        let end_state = plain::run(&mut game, io::stdin().lock(), io::stdout());
+        save_game(play.save.as_deref(), &game)?;
+        return match end_state {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                eprintln!("{}", error.to_string().red());
+                Err(1)
+            }
+        };
+    }
+
+    let mut app = ui::App::new(game).with_config(ui_config);
+    let end_state = run_ui(&mut app)?;
+    match end_state {
+        ui::AppEndState::Terminated { signal } => {
+            // Keep the game even without a replay file, it could not be played on otherwise
+            let path = play.save.clone().unwrap_or_else(|| {
+                env::temp_dir().join("word_game_interrupted.txt").display().to_string()
+            });
+            if !app.game().is_over() {
+                save_game(Some(&path), app.game())?;
+                eprintln!("{}", format!("Game saved, resume it with --save {path}").yellow());
+            }
+            Err(128 + signal)
+        },
+        ui::AppEndState::Close { forced: true } => {
+            save_game(play.save.as_deref(), app.game())?;
+            Err(130)
+        },
+        ui::AppEndState::Won | ui::AppEndState::Lost | ui::AppEndState::Close { forced: false } =>
+            save_game(play.save.as_deref(), app.game())
+    }
+}
+
+
+/// The words that can be guessed and the words never picked as the answer
+fn apply_blocklists(words: Dictionary, args: &BlocklistArgs) -> Result<(Dictionary, Dictionary), i32> {
+    let mut blocklist: Vec<String> =
+        if !args.no_default_blocklist {
+            words::default_blocklist().iter().map(str::to_string).collect()
+        }
+        else {
+            Vec::new()
+        };
+    if let Some(path) = &args.blocklist {
+        match words::read_blocklist(path) {
+            Ok(words) =>
+                blocklist.extend(words.iter().map(str::to_string)),
+            Err(error) => {
+                eprintln!("{}", format!("{path}: {error}").red());
+                return Err(1);
+            }
+        }
+    }
+    let blocklist = Dictionary::from(blocklist);
+    let words =
+        if args.block_guesses {
+            words.without(&blocklist)
+        }
+        else {
+            words
+        };
+    Ok((words, blocklist))
+}
+
+/// Unfinished game saved in the replay file, `None` to start a new game when there is no file yet
+/// or its game is over. A file that cannot be loaded is an error, so that it is not overwritten
+fn resume_game(path: Option<&str>, words: &Dictionary) -> Result<Option<wordle::WordleGame>, i32> {
+    let path = match path {
+        Some(path) => path,
+        None => return Ok(None)
+    };
+    let report = |error: &dyn std::fmt::Display| {
+        eprintln!("{}", format!("{path}: {error}, use another replay file to start a new game").red());
+        1
+    };
+
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(report(&error))
+    };
+    let record = history::parse(&content).map_err(|error| report(&error))?;
+    match wordle::WordleGame::from_record(words.clone(), &record) {
+        Ok(game) if !game.is_over() => {
+            eprintln!("{}", format!("Resuming the game saved in {path}").yellow());
+            Ok(Some(game))
+        },
+        Ok(_) => Ok(None),
+        Err(error) => Err(report(&error))
+    }
+}
+
+/// Writes the record of the game to the replay file, if any
+fn save_game(path: Option<&str>, game: &wordle::WordleGame) -> Result<(), i32> {
+    match path {
+        Some(path) => fs::write(path, history::to_text(&game.record())).map_err(|error| {
+            eprintln!("{}", format!("{path}: {error}").red());
+            1
+        }),
+        None => Ok(())
+    }
+}
+
+
+fn replay_game(game: &wordle::WordleGame, ui_config: ui::UiConfig) -> Result<(), i32> {
+    let mut replay = ui::Replay::new(game).with_config(ui_config);
+    match run_ui(&mut replay)? {
+        ui::AppEndState::Terminated { signal } => Err(128 + signal),
+        ui::AppEndState::Close { forced: true } => Err(130),
+        _ => Ok(())
+    }
+}
+
+/// Shows `screen` in the terminal until it ends or a signal terminates the game,
+/// suspending it on Ctrl+Z and SIGTSTP
+fn run_ui<S: Screen>(screen: &mut S) -> Result<ui::AppEndState, i32> {
+    let events = ui::TerminalEvents::new();
+    let terminal = ui::start_ui(CrosstermBackend::new(io::stdout()));
+    let (mut events, mut terminal) = if let (Ok(events), Ok(terminal)) = (events, terminal) {
+        (events, terminal)
+    } else {
+        eprintln!("{}", Colorize::red("Can't initialize TUI session"));
+        return Err(1);
+    };
+
+    let mut next_tick = Instant::now() + TICK;
+    let mut redraw = true;
+    loop {
+        if redraw {
+            terminal.draw(|f| screen.render(f)).unwrap();
+            redraw = false;
+        }
+
+        match events.interrupt() {
+            Some(ui::Interrupt::Suspend) => {
+                ui::suspend_ui(&mut terminal).unwrap();
+                redraw = true;
+                continue;
+            },
+            Some(ui::Interrupt::Terminate { signal }) => {
+                ui::end_ui(terminal).unwrap();
+                return Ok(ui::AppEndState::Terminated { signal });
+            },
+            None => ()
+        }
+        // Wakes up at every tick, to animate and to notice the signals
+        if events.poll(next_tick.saturating_duration_since(Instant::now())).unwrap_or(false) {
+            screen.update(&mut events);
+            redraw = true;
+        }
+        if Instant::now() >= next_tick {
+            redraw |= screen.tick();
+            next_tick = Instant::now() + TICK;
+        }
+
+        if let ui::AppState::End(end_state) = screen.state() {
+            ui::end_ui(terminal).unwrap();
+            return Ok(end_state);
+        }
+    }
+}
+
+
+/// Reads the config file, reporting why it cannot be used
+fn load_config(path: Option<&Path>) -> Result<Config, i32> {
+    Config::load(path).map_err(|error| {
+        let path = path.map(Path::to_path_buf).or_else(Config::default_path).unwrap_or_default();
+        eprintln!("{}", format!("{}: {error}", path.display()).red());
+        1
+    })
+}
+
+/// Loads the theme of the interface, reporting why it cannot be used
+fn load_ui_config(settings: &Settings) -> Result<ui::UiConfig, i32> {
+    settings.ui_config().map_err(|error| {
+        eprintln!("{}", error.to_string().red());
+        1
+    })
+}
+
+
+/// Reads and merges the word lists from the arguments, reporting errors of every file.
+/// When `confirm` is set and some files have errors, waits for the player before continuing.
+fn load_words(args: &WordsArgs, confirm: bool) -> Result<words::WordList, i32> {
+    let (mut list, errors) = words::read_all(&args.words, &args.excludes);
+    for (path, error) in &errors {
+        eprintln!("{}", format!("{path}: {error}").yellow());
+    }
+    if let Some(length) = args.length {
+        list.words = list.words.filter(|word| word.chars().count() == length);
+    }
+
+    if list.words.is_empty() {
+        eprintln!("{}", Colorize::red("No word list to play with"));
+        Err(1)
+    }
+    else {
+        if confirm && !errors.is_empty() {
+            eprintln!("{}", Colorize::yellow("There are still words left in the word list, playing"));
+            eprintln!("{}", Colorize::yellow("Press ENTER to continue"));
+            // A closed or empty input continues at once
+            let _ = io::stdin().read(&mut [0]);
+        }
+        Ok(list)
+    }
+}
@@ -0,0 +1,88 @@
+use std::io::{self, BufRead, Write};
+
+use colored::{ColoredString, Colorize};
+
+use crate::ui::AppEndState;
+use word_game::{
+    pattern::Pattern,
+    wordle::{LetterScore, WordleGame}
+};
+
+
+
+const ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+
+
+/// Plays `game` reading a guess per line from `input`, without raw mode or the alternate screen.
+/// Every guess is printed coloured and followed by its feedback in the `solve` notation,
+/// so the game stays readable without colours
+pub fn run<R, W>(game: &mut WordleGame, input: R, mut output: W) -> io::Result<AppEndState>
+    where R: BufRead, W: Write
+{
+    let length = game.guess_empty().len();
+    writeln!(output, "Guess the {length} letter word, {} attempts", game.lives())?;
+    writeln!(output, "Feedback: g is a correct letter, y is a present letter, . is a wrong letter")?;
+
+    let mut lines = input.lines();
+    while !game.is_over() {
+        write!(output, "> ")?;
+        output.flush()?;
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => {
+                writeln!(output)?;
+                return Ok(AppEndState::Close { forced: false });
+            }
+        };
+        let guess = line.trim();
+        if guess.is_empty() {
+            continue;
+        }
+
+        match game.guess(guess) {
+            Ok(scores) => {
+                let word = &game.tries().last().unwrap().0;
+                writeln!(output, "{}  {scores}", colored_word(word, &scores))?;
+                if game.is_won() {
+                    writeln!(output, "{}", format!("You won in {} guesses", game.tries().len()).green())?;
+                    return Ok(AppEndState::Won);
+                }
+                writeln!(output, "Letters: {}  ({} attempts left)", remaining_letters(game), game.lives())?;
+            },
+            Err(error) =>
+                writeln!(output, "{}", error.to_string().red())?
+        }
+    }
+
+    writeln!(output, "{}", format!("You lost, the word was {}", game.answer().to_uppercase()).red())?;
+    Ok(AppEndState::Lost)
+}
+
+
+fn colored_letter(letter: char, score: LetterScore) -> ColoredString {
+    let letter = letter.to_uppercase().to_string();
+    match score {
+        LetterScore::Unknown => letter.normal(),
+        LetterScore::Wrong => letter.dimmed(),
+        LetterScore::Present => letter.black().on_yellow(),
+        LetterScore::Correct => letter.black().on_green()
+    }
+}
+
+fn colored_word(word: &str, scores: &Pattern) -> String {
+    word.chars()
+        .zip(scores.iter())
+        .map(|(letter, score)| colored_letter(letter, score).to_string())
+        .collect()
+}
+
+/// Letters that are not known to be wrong, coloured by what is known about them
+fn remaining_letters(game: &WordleGame) -> String {
+    ALPHABET
+        .chars()
+        .zip(game.known_guesses(ALPHABET))
+        .filter(|(_, score)| *score != LetterScore::Wrong)
+        .map(|(letter, score)| colored_letter(letter, score).to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,34 @@
+use colored::Colorize;
+
+use word_game::dictionary::Dictionary;
+use word_game::solver;
+
+
+
+/// Prints the words matching the feedback of `guesses`, at most `limit` of them, and the suggested next guess
+pub fn run(words: &Dictionary, guesses: &[String], limit: usize) -> Result<(), i32> {
+    let mut feedback = Vec::new();
+    for guess in guesses {
+        match solver::parse_feedback(guess) {
+            Ok(parsed) =>
+                feedback.push(parsed),
+            Err(error) => {
+                eprintln!("{}", error.red());
+                return Err(1);
+            }
+        }
+    }
+
+    let candidates = solver::candidates(words, &feedback);
+    println!("{} matching words", candidates.len());
+    for word in candidates.iter().take(limit) {
+        println!("{word}");
+    }
+    if candidates.len() > limit {
+        println!("...");
+    }
+    if let Some(suggestion) = solver::suggest(&candidates) {
+        println!("{}", format!("Suggested guess: {suggestion}").green());
+    }
+    Ok(())
+}
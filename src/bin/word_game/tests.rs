@@ -0,0 +1,440 @@
+use std::{
+    collections::VecDeque,
+    env,
+    fs,
+    path::PathBuf
+};
+
+use clap::Parser;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use tui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    style::Color,
+    Terminal
+};
+
+use word_game::{
+    dictionary::Dictionary,
+    history::{self, HistoryError},
+    words,
+    wordle::{GameConfig, LetterScore, WordleGame}
+};
+
+use crate::args::{Args, Command};
+use crate::config::{Config, ConfigError, Source};
+use crate::plain;
+use crate::theme::{Colors, Theme, ThemeError};
+use crate::ui::{
+    App, AppEndState, AppState, Drawable, KeyBinding, KeyboardLayout, LetterBoxWord, Replay, Screen, UiConfig
+};
+
+#[test]
+fn config_test() {
+    let config = Config::parse("
+        length = 5
+        attempts = 8
+        hard = true
+        layout = \"dvorak\"
+
+        [keys]
+        submit = \"ctrl+s\"
+    ").unwrap();
+    let play = match Args::try_parse_from(["word_game", "--attempts", "3", "--no-hard"]).unwrap().subcommand() {
+        Command::Play(play) => play,
+        command => panic!("Expected to play, got {command:?}")
+    };
+    let settings = config.settings(&play);
+
+    assert_eq!((settings.length.value, settings.length.source), (Some(5), Source::ConfigFile));
+    assert_eq!((settings.attempts.value, settings.attempts.source), (Some(3), Source::CommandLine));
+    assert_eq!((settings.hard.value, settings.hard.source), (false, Source::CommandLine));
+    assert_eq!((settings.words.value.len(), settings.words.source), (0, Source::Default));
+    assert_eq!(settings.layout.value, KeyboardLayout::Dvorak);
+    assert_eq!(settings.ui_config().unwrap().keys.submit, KeyBinding::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+    assert_eq!(settings.apply(&play).words.length, Some(5));
+    assert!(Args::try_parse_from(["word_game", "--attempts", "0"]).is_err());
+
+    // Word lists can still be given without --words, as before the subcommands
+    match Args::try_parse_from(["word_game", "list.txt", "--words", "more.txt"]).unwrap().subcommand() {
+        Command::Play(play) => assert_eq!(play.words.words, ["list.txt", "more.txt"]),
+        command => panic!("Expected to play, got {command:?}")
+    }
+    match Args::try_parse_from(["word_game", "base.txt", "jargon.txt"]).unwrap().subcommand() {
+        Command::Play(play) => assert_eq!(play.words.words, ["base.txt", "jargon.txt"]),
+        command => panic!("Expected to play, got {command:?}")
+    }
+
+    assert!(matches!(Config::parse("hard = \"yes\""), Err(ConfigError::Invalid { .. })));
+    assert!(matches!(Config::parse("colour = \"red\""), Err(ConfigError::Invalid { .. })));
+    assert!(matches!(Config::parse("[keys]\nquit = \"hyper+q\""), Err(ConfigError::Invalid { .. })));
+}
+
+#[test]
+fn replay_test() {
+    // A game saved without the blocked guesses is replayed with the same blocklists
+    let blocked = words::default_blocklist().iter().next().unwrap().to_string();
+    let words: Dictionary = vec!["those".to_string(), "tiles".to_string(), blocked].into();
+    let play = match Args::try_parse_from(["word_game", "--block-guesses"]).unwrap().subcommand() {
+        Command::Play(play) => play,
+        command => panic!("Expected to play, got {command:?}")
+    };
+    let (guesses, _) = crate::apply_blocklists(words.clone(), &play.blocklists).unwrap();
+    let mut game = WordleGame::new_with_answer(guesses, "those").unwrap();
+    game.guess("tiles").unwrap();
+    let record = history::parse(&history::to_text(&game.record())).unwrap();
+
+    let replay = match Args::try_parse_from(["word_game", "replay", "--block-guesses", "game.txt"]).unwrap().subcommand() {
+        Command::Replay(replay) => replay,
+        command => panic!("Expected to replay, got {command:?}")
+    };
+    let (guesses, _) = crate::apply_blocklists(words.clone(), &replay.blocklists).unwrap();
+    assert_eq!(WordleGame::from_record(guesses, &record).unwrap().tries(), game.tries());
+    assert!(matches!(WordleGame::from_record(words, &record), Err(HistoryError::OtherWords { .. })));
+}
+
+#[test]
+fn key_binding_test() {
+    for (text, code, modifiers) in [
+        ("enter", KeyCode::Enter, KeyModifiers::NONE),
+        ("Ctrl+W", KeyCode::Char('w'), KeyModifiers::CONTROL),
+        ("ctrl+alt+backspace", KeyCode::Backspace, KeyModifiers::CONTROL | KeyModifiers::ALT),
+        ("ctrl++", KeyCode::Char('+'), KeyModifiers::CONTROL),
+        ("f5", KeyCode::F(5), KeyModifiers::NONE)
+    ] {
+        let binding: KeyBinding = text.parse().unwrap();
+        assert_eq!(binding, KeyBinding::new(code, modifiers));
+        assert_eq!(binding.to_string().parse::<KeyBinding>(), Ok(binding));
+    }
+    assert!("ctrl+".parse::<KeyBinding>().is_err());
+    assert!("enterr".parse::<KeyBinding>().is_err());
+}
+
+#[test]
+fn plain_test() {
+    colored::control::set_override(false);
+    let words: Dictionary = ["those", "geese", "thorn", "shoes", "tiles"].map(String::from).to_vec().into();
+
+    let mut game = WordleGame::new_with_answer(words.clone(), "those").unwrap();
+    let mut output = Vec::new();
+    let end = plain::run(&mut game, "tiles\n\nhello\nThose\n".as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(end, AppEndState::Won);
+    assert!(output.contains("TILES  g..yy"));
+    assert!(output.contains("This word is not in a dictionary"));
+    assert!(output.contains("Letters: A B C D E F G H J K M N O P Q R S T U V W X Y Z  (4 attempts left)"));
+    assert!(output.contains("You won in 2 guesses"));
+
+    let mut game = WordleGame::new_with_answer(words.clone(), "those").unwrap()
+        .with_config(GameConfig { attempts: Some(1), hard: false })
+        .unwrap();
+    let mut output = Vec::new();
+    assert_eq!(plain::run(&mut game, "geese\n".as_bytes(), &mut output).unwrap(), AppEndState::Lost);
+    assert!(String::from_utf8(output).unwrap().contains("the word was THOSE"));
+
+    let mut game = WordleGame::new_with_answer(words, "those").unwrap();
+    assert_eq!(plain::run(&mut game, "".as_bytes(), Vec::new()).unwrap(), AppEndState::Close { forced: false });
+}
+
+/// Key presses typing `text`, with `\n` for Enter
+fn keys(text: &str) -> VecDeque<Event> {
+    text.chars()
+        .map(|c| match c {
+            '\n' => KeyCode::Enter,
+            c => KeyCode::Char(c)
+        })
+        .map(|code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+        .collect()
+}
+
+/// App of `game` without animations, drawn as it is after each event
+fn still_app(game: WordleGame) -> App {
+    App::new(game).with_config(UiConfig { animations: false, ..Default::default() })
+}
+
+fn draw<D: Drawable>(drawable: &D) -> Buffer {
+    draw_sized(drawable, 80, 45)
+}
+
+fn draw_sized<D: Drawable>(drawable: &D, width: u16, height: u16) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| drawable.render(f)).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+fn line(buffer: &Buffer, y: u16) -> String {
+    (0..buffer.area.width).map(|x| buffer.get(x, y).symbol.as_str()).collect()
+}
+
+/// Letters drawn on line `y` inside the main box, with their background
+fn letters(buffer: &Buffer, y: u16) -> Vec<(char, Color)> {
+    (1..buffer.area.width - 1)
+        .map(|x| buffer.get(x, y))
+        .filter_map(|cell| cell.symbol.chars().next().filter(char::is_ascii_uppercase).map(|c| (c, cell.bg)))
+        .collect()
+}
+
+#[test]
+fn app_test() {
+    use Color::{Black, DarkGray, Green, Yellow};
+
+    let words: Dictionary = ["those", "geese", "thorn", "shoes", "tiles"].map(String::from).to_vec().into();
+    let mut app = still_app(WordleGame::new_with_answer(words.clone(), "those").unwrap());
+    // Rows of the board are 4 lines apart from line 3, the keyboard rows are at the bottom
+    let (board, keyboard, error) = (|row: u16| 3 + 4 * row, [33, 37, 41], 30);
+
+    let mut events = keys("tiles\nhello");
+    while !events.is_empty() {
+        app.update(&mut events);
+    }
+    let buffer = draw(&app);
+    assert_eq!(
+        letters(&buffer, board(0)),
+        [('T', Green), ('I', Black), ('L', Black), ('E', Yellow), ('S', Yellow)]
+    );
+    assert_eq!(letters(&buffer, board(1)), [('H', DarkGray), ('E', DarkGray), ('L', DarkGray), ('L', DarkGray), ('O', DarkGray)]);
+    assert!(letters(&buffer, board(2)).is_empty());
+    let keyboard_colors: Vec<(char, Color)> = keyboard.iter().flat_map(|y| letters(&buffer, *y)).collect();
+    assert_eq!(keyboard_colors.len(), 26);
+    for (letter, color) in [('T', Green), ('E', Yellow), ('I', Black), ('A', DarkGray)] {
+        assert!(keyboard_colors.contains(&(letter, color)), "{letter} should be {color:?}");
+    }
+    assert!(line(&buffer, error).trim_matches(['│', ' ']).is_empty());
+
+    app.handle(keys("\n")[0].clone());
+    let buffer = draw(&app);
+    assert_eq!(line(&buffer, error).trim_matches(['│', ' ']), "This word is not in a dictionary");
+    assert_eq!(buffer.get(40, error).fg, Color::Red);
+    assert!(letters(&buffer, board(1)).iter().all(|(_, color)| *color == DarkGray));
+
+    let mut events = keys("those\n");
+    while !events.is_empty() {
+        app.update(&mut events);
+    }
+    let buffer = draw(&app);
+    assert!(line(&buffer, error).trim_matches(['│', ' ']).is_empty());
+    assert_eq!(letters(&buffer, board(1)).iter().map(|(c, _)| *c).collect::<String>(), "THOSE");
+    assert!(letters(&buffer, board(1)).iter().all(|(_, color)| *color == Green));
+    assert!(app.game().is_won());
+
+    app.handle(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)));
+    assert_eq!(app.state(), AppState::End(AppEndState::Close { forced: false }));
+
+    // The replay of the game shows the board after each event
+    let mut replay = Replay::new(app.game());
+    assert!(letters(&draw(&replay), board(0)).iter().all(|(_, color)| *color == DarkGray));
+    replay.handle(Event::Key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE)));
+    let buffer = draw(&replay);
+    assert_eq!(letters(&buffer, board(0))[0], ('T', Green));
+    assert!(line(&buffer, error).contains("2/4: guessed tiles g..yy"));
+    replay.handle(Event::Key(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE)));
+    assert!(line(&draw(&replay), error).contains("1/4: created those"));
+}
+
+#[test]
+fn animation_test() {
+    use Color::{Black, DarkGray, Green, Yellow};
+
+    let words: Dictionary = ["those", "tiles"].map(String::from).to_vec().into();
+    let mut app = App::new(WordleGame::new_with_answer(words, "those").unwrap());
+    let board = |row: u16| 3 + 4 * row;
+    let tick_until_still = |app: &mut App| {
+        let mut ticks = 0;
+        while app.tick() {
+            ticks += 1;
+            assert!(ticks < 100, "The animations should end");
+        }
+    };
+
+    // The letter just typed pops out of its box
+    app.handle(keys("t")[0].clone());
+    assert_eq!(line(&draw(&app), board(0) - 1).trim_matches(['│', ' ']), "┏━━━┓");
+    tick_until_still(&mut app);
+    assert!(line(&draw(&app), board(0) - 1).trim_matches(['│', ' ']).is_empty());
+
+    // Tiles flip one by one to reveal their scores
+    let mut events = keys("iles
+");
+    while !events.is_empty() {
+        app.update(&mut events);
+    }
+    assert!(letters(&draw(&app), board(0)).iter().all(|(_, color)| *color == DarkGray));
+    for _ in 0..4 {
+        app.tick();
+    }
+    let revealed = letters(&draw(&app), board(0));
+    assert_eq!(revealed[0], ('T', Green));
+    assert_eq!(revealed[4], ('S', DarkGray));
+    tick_until_still(&mut app);
+    assert_eq!(
+        letters(&draw(&app), board(0)),
+        [('T', Green), ('I', Black), ('L', Black), ('E', Yellow), ('S', Yellow)]
+    );
+
+    // A refused word shakes sideways on its row
+    let mut events = keys("thosx
+");
+    while !events.is_empty() {
+        app.update(&mut events);
+    }
+    let shaken = line(&draw(&app), board(1));
+    app.tick();
+    assert_ne!(line(&draw(&app), board(1)), shaken);
+    assert!(letters(&draw(&app), board(1)).iter().map(|(c, _)| *c).eq("THOSX".chars()));
+    tick_until_still(&mut app);
+    assert!(letters(&draw(&app), board(1)).is_empty());
+
+    // The winning row bounces up once flipped
+    let mut events = keys("those
+");
+    while !events.is_empty() {
+        app.update(&mut events);
+    }
+    let mut bounced = false;
+    while app.tick() {
+        bounced |= letters(&draw(&app), board(1) - 1).contains(&('T', Green));
+    }
+    assert!(bounced);
+    assert!(letters(&draw(&app), board(1)).iter().all(|(_, color)| *color == Green));
+
+    // Without animations every change is drawn at once
+    let mut app = still_app(app.game().clone());
+    assert!(!app.tick());
+    assert!(letters(&draw(&app), board(1)).iter().all(|(_, color)| *color == Green));
+}
+
+#[test]
+fn theme_test() {
+    use Color::{Black, Green, Magenta, Rgb, White};
+
+    assert_eq!("white on green".parse(), Ok(Colors::new(White, Green)));
+    assert_eq!("Dark-Gray".parse(), Ok(Colors::fg(Color::DarkGray)));
+    assert_eq!("on #538d4e".parse(), Ok(Colors { fg: None, bg: Some(Rgb(0x53, 0x8d, 0x4e)) }));
+    assert_eq!("236 on 16".parse(), Ok(Colors::new(Color::Indexed(236), Color::Indexed(16))));
+    for invalid in ["", "white on", "white green", "purple", "#53zz4e", "#aébcd"] {
+        assert!(invalid.parse::<Colors>().is_err(), "'{invalid}' should not be colours");
+    }
+
+    for name in Theme::BUILT_IN {
+        assert_eq!(Theme::load(name).unwrap().name, name);
+    }
+    let theme = Theme::parse("correct = \"black on #f5793a\"\nborder = \"blue\"\nsize-short = \"magenta\"").unwrap();
+    assert_eq!(theme.correct, Colors::new(Black, Rgb(0xf5, 0x79, 0x3a)));
+    assert_eq!(theme.size_short, Colors::fg(Magenta));
+    assert_eq!(theme.present, Theme::default().present);
+    assert!(Theme::parse("tiles = \"red\"").is_err());
+    assert!(Theme::parse("error = \"redish\"").is_err());
+    assert!(matches!(Theme::load("no/such/theme.toml"), Err(ThemeError::Unreadable { .. })));
+
+    // A theme from a file comes first when switching themes
+    let custom = Theme { name: "custom".to_string(), ..theme };
+    let cycle: Vec<String> = custom.cycle().into_iter().map(|theme| theme.name).collect();
+    assert_eq!(cycle, ["custom", "classic", "dark", "light", "high-contrast", "monochrome"]);
+    assert_eq!(Theme::default().cycle().len(), Theme::BUILT_IN.len());
+
+    // The theme key switches the colours of the tiles and keyboard while playing
+    let words: Dictionary = ["those", "tiles"].map(String::from).to_vec().into();
+    let mut app = still_app(WordleGame::new_with_answer(words, "those").unwrap());
+    let mut events = keys("tiles\n");
+    while !events.is_empty() {
+        app.update(&mut events);
+    }
+    assert_eq!(letters(&draw(&app), 3)[0], ('T', Green));
+    let theme_key = Event::Key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+    app.handle(theme_key.clone());
+    let buffer = draw(&app);
+    assert_eq!(letters(&buffer, 3)[0], ('T', Rgb(0x53, 0x8d, 0x4e)));
+    assert_eq!(buffer.get(0, 0).bg, Rgb(0x12, 0x12, 0x13));
+    assert!(line(&buffer, 30).contains("Theme dark"));
+    for _ in 0..3 {
+        app.handle(theme_key.clone());
+    }
+    // Monochrome tells the scores apart by the borders of the tiles only
+    let buffer = draw(&app);
+    assert!(letters(&buffer, 3).iter().all(|(_, color)| *color == Color::Reset));
+    assert!(line(&buffer, 2).contains("┏━━━┓"));
+    app.handle(theme_key);
+    assert_eq!(letters(&draw(&app), 3)[0], ('T', Green));
+}
+
+/// Compares `buffer` to the fixture `snapshots/{name}.txt`: its text, then a blank line and the background
+/// of each cell, `g` green, `y` yellow, `-` dark gray, `.` black and a space for none.
+/// Run the tests with `UPDATE_SNAPSHOTS=1` to write the fixtures after an intended change
+fn assert_snapshot(name: &str, buffer: &Buffer) {
+    let area = buffer.area;
+    let text: Vec<String> = (0..area.height).map(|y| line(buffer, y).trim_end().to_string()).collect();
+    let backgrounds: Vec<String> = (0..area.height)
+        .map(|y| (0..area.width)
+            .map(|x| match buffer.get(x, y).bg {
+                Color::Green => 'g',
+                Color::Yellow => 'y',
+                Color::DarkGray => '-',
+                Color::Black => '.',
+                Color::Reset => ' ',
+                _ => '?'
+            })
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+        )
+        .collect();
+    let snapshot = format!("{}\n\n{}\n", text.join("\n"), backgrounds.join("\n"));
+
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "src", "bin", "word_game", "snapshots", &format!("{name}.txt")]
+        .iter()
+        .collect();
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, snapshot).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("No snapshot {}, run the tests with UPDATE_SNAPSHOTS=1 to write it", path.display()));
+    assert!(expected == snapshot, "{name} does not match its snapshot:\n{snapshot}");
+}
+
+#[test]
+fn snapshot_test() {
+    let words: Dictionary = ["tide", "tile", "those", "tiles", "thorns", "titles"].map(String::from).to_vec().into();
+
+    for (answer, guesses, (width, height)) in [
+        ("tide", "tile\nti", (72, 39)),
+        ("those", "tiles\nthose\n", (80, 45)),
+        ("those", "", (120, 50)),
+        ("titles", "thorns\ntit", (76, 47))
+    ] {
+        let mut app = still_app(WordleGame::new_with_answer(words.clone(), answer).unwrap());
+        let mut events = keys(guesses);
+        while !events.is_empty() {
+            app.update(&mut events);
+        }
+        assert_snapshot(&format!("app_{answer}_{width}x{height}"), &draw_sized(&app, width, height));
+    }
+
+    // One line or column short, and far too small: no underflow when centering
+    let app = App::new(WordleGame::new_with_answer(words.clone(), "titles").unwrap());
+    for (width, height) in [(75, 47), (76, 46), (20, 6)] {
+        assert_snapshot(&format!("too_small_{width}x{height}"), &draw_sized(&app, width, height));
+    }
+    let app = App::new(
+        WordleGame::new_with_answer(words, "tide")
+            .and_then(|game| game.with_config(GameConfig { attempts: Some(12), hard: false }))
+            .unwrap()
+    );
+    assert_snapshot("too_small_tide_72x39", &draw_sized(&app, 72, 39));
+
+    for word in ["tide", "those", "titles"] {
+        let scores = [LetterScore::Correct, LetterScore::Present, LetterScore::Wrong, LetterScore::Unknown]
+            .repeat(2);
+        let word_box = LetterBoxWord {
+            pos: (1, 1),
+            word,
+            scores: &scores,
+            counts: &[2, 0, 1, 3],
+            effects: &[],
+            theme: &Theme::default()
+        };
+        // Boxes are 5 columns wide with 2 columns between them
+        assert_snapshot(&format!("letter_box_word_{word}"), &draw_sized(&word_box, 7 * word.len() as u16, 5));
+    }
+}
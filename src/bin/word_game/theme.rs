@@ -0,0 +1,262 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::Path,
+    str::FromStr
+};
+
+use serde::Deserialize;
+use tui::style::{Color, Style};
+
+
+
+/// Foreground and background colours, written like `white on green`, `red` or `on black`.
+/// A missing colour keeps the one underneath
+#[derive(
+    Debug, Default,
+    Clone, Copy,
+    PartialEq, Eq,
+    Deserialize
+)]
+#[serde(try_from = "String")]
+pub struct Colors {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>
+}
+
+impl Colors {
+    pub const fn new(fg: Color, bg: Color) -> Self {
+        Self { fg: Some(fg), bg: Some(bg) }
+    }
+
+    pub const fn fg(fg: Color) -> Self {
+        Self { fg: Some(fg), bg: None }
+    }
+
+    pub const fn bg(bg: Color) -> Self {
+        Self { fg: None, bg: Some(bg) }
+    }
+
+    pub fn style(&self) -> Style {
+        Style {
+            fg: self.fg,
+            bg: self.bg,
+            ..Style::default()
+        }
+    }
+}
+
+impl FromStr for Colors {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim().to_lowercase();
+        let words: Vec<&str> = value.split_whitespace().collect();
+        let (fg, bg) = match words.as_slice() {
+            [fg] => (Some(*fg), None),
+            ["on", bg] => (None, Some(*bg)),
+            [fg, "on", bg] => (Some(*fg), Some(*bg)),
+            _ => return Err(format!("Expected colours like 'white on green', got '{value}'"))
+        };
+        Ok(Self {
+            fg: fg.map(parse_color).transpose()?,
+            bg: bg.map(parse_color).transpose()?
+        })
+    }
+}
+
+impl TryFrom<String> for Colors {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// A colour of the terminal palette like `dark-gray`, `#rrggbb` or an index of the 256 colours
+fn parse_color(name: &str) -> Result<Color, String> {
+    let color = match name.replace(['-', '_'], "").as_str() {
+        "reset" | "default" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        hex if hex.len() == 7 && hex.is_ascii() && hex.starts_with('#') => {
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| format!("Invalid hexadecimal colour '{name}'"));
+            Color::Rgb(channel(1)?, channel(3)?, channel(5)?)
+        },
+        index if index.parse::<u8>().is_ok() => Color::Indexed(index.parse().unwrap()),
+        _ => return Err(format!("Unknown colour '{name}'"))
+    };
+    Ok(color)
+}
+
+
+#[derive(Debug)]
+pub enum ThemeError {
+    Unreadable {
+        name: String,
+        reason: String
+    },
+    Invalid {
+        name: String,
+        reason: String
+    }
+}
+
+impl Display for ThemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unreadable { name, reason } => write!(
+                f,
+                "Theme '{name}' is not one of {} and its file cannot be read: {reason}",
+                Theme::BUILT_IN.join(", ")
+            ),
+            Self::Invalid { name, reason } => write!(f, "Theme file {name} cannot be parsed: {reason}")
+        }
+    }
+}
+
+
+/// Colours of the interface, read from a TOML file with the same keys as the fields.
+/// Missing keys keep the colours of the classic theme
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Theme {
+    /// Built-in name or stem of the file
+    #[serde(skip)]
+    pub name: String,
+    /// Text and background of the board and of its notices
+    pub text: Colors,
+    pub border: Colors,
+    pub error: Colors,
+    /// Notice shown instead of the board when the terminal is too small, with its width and height
+    /// coloured by whether they are too small or large enough
+    pub too_small: Colors,
+    pub size_short: Colors,
+    pub size_enough: Colors,
+    /// Tiles of the board and of the keyboard, by score
+    pub correct: Colors,
+    pub present: Colors,
+    pub wrong: Colors,
+    pub unknown: Colors
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: "classic".to_string(),
+            text: Colors::default(),
+            border: Colors::default(),
+            error: Colors::fg(Color::Red),
+            too_small: Colors::bg(Color::Black),
+            size_short: Colors::fg(Color::LightRed),
+            size_enough: Colors::fg(Color::LightGreen),
+            correct: Colors::new(Color::White, Color::Green),
+            present: Colors::new(Color::White, Color::Yellow),
+            wrong: Colors::new(Color::White, Color::Black),
+            unknown: Colors::new(Color::White, Color::DarkGray)
+        }
+    }
+}
+
+impl Theme {
+    pub const BUILT_IN: [&'static str; 5] = ["classic", "dark", "light", "high-contrast", "monochrome"];
+
+    pub fn built_in(name: &str) -> Option<Self> {
+        let rgb = |rgb: u32| Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8);
+        let theme = match name {
+            "classic" => Self::default(),
+            "dark" => Self {
+                text: Colors::new(rgb(0xd7dadc), rgb(0x121213)),
+                border: Colors::fg(rgb(0x565758)),
+                error: Colors::fg(rgb(0xf26b6b)),
+                too_small: Colors::new(rgb(0xd7dadc), rgb(0x121213)),
+                size_short: Colors::fg(rgb(0xf26b6b)),
+                size_enough: Colors::fg(rgb(0x538d4e)),
+                correct: Colors::new(Color::White, rgb(0x538d4e)),
+                present: Colors::new(Color::White, rgb(0xb59f3b)),
+                wrong: Colors::new(Color::White, rgb(0x3a3a3c)),
+                unknown: Colors::new(Color::White, rgb(0x818384)),
+                ..Self::default()
+            },
+            "light" => Self {
+                text: Colors::new(rgb(0x1a1a1b), rgb(0xffffff)),
+                border: Colors::fg(rgb(0x878a8c)),
+                error: Colors::fg(rgb(0xc0392b)),
+                too_small: Colors::new(rgb(0x1a1a1b), rgb(0xffffff)),
+                size_short: Colors::fg(rgb(0xc0392b)),
+                size_enough: Colors::fg(rgb(0x6aaa64)),
+                correct: Colors::new(Color::White, rgb(0x6aaa64)),
+                present: Colors::new(Color::White, rgb(0xc9b458)),
+                wrong: Colors::new(Color::White, rgb(0x787c7e)),
+                unknown: Colors::new(rgb(0x1a1a1b), rgb(0xd3d6da)),
+                ..Self::default()
+            },
+            // Orange and blue stay apart for every kind of colour blindness
+            "high-contrast" => Self {
+                error: Colors::fg(rgb(0xf5793a)),
+                size_short: Colors::fg(rgb(0xf5793a)),
+                size_enough: Colors::fg(rgb(0x85c0f9)),
+                correct: Colors::new(Color::Black, rgb(0xf5793a)),
+                present: Colors::new(Color::Black, rgb(0x85c0f9)),
+                ..Self::default()
+            },
+            // The borders of the tiles still tell the scores apart
+            "monochrome" => Self {
+                error: Colors::default(),
+                too_small: Colors::default(),
+                size_short: Colors::default(),
+                size_enough: Colors::default(),
+                correct: Colors::default(),
+                present: Colors::default(),
+                wrong: Colors::default(),
+                unknown: Colors::default(),
+                ..Self::default()
+            },
+            _ => return None
+        };
+        Some(Self { name: name.to_string(), ..theme })
+    }
+
+    /// The built-in theme `name`, or else the theme file at the path `name`
+    pub fn load(name: &str) -> Result<Self, ThemeError> {
+        if let Some(theme) = Self::built_in(name) {
+            return Ok(theme);
+        }
+        let content = fs::read_to_string(name)
+            .map_err(|error| ThemeError::Unreadable { name: name.to_string(), reason: error.to_string() })?;
+        let theme = Self::parse(&content)
+            .map_err(|reason| ThemeError::Invalid { name: name.to_string(), reason })?;
+        let stem = Path::new(name).file_stem().map(|stem| stem.to_string_lossy().to_string());
+        Ok(Self { name: stem.unwrap_or_default(), ..theme })
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|error| error.to_string())
+    }
+
+    /// Themes to switch between while playing: this one, then the built-in ones
+    pub fn cycle(&self) -> Vec<Self> {
+        let built_in = Self::BUILT_IN.iter().filter_map(|name| Self::built_in(name));
+        if Self::BUILT_IN.contains(&self.name.as_str()) {
+            built_in.collect()
+        }
+        else {
+            Some(self.clone()).into_iter().chain(built_in).collect()
+        }
+    }
+}
@@ -0,0 +1,1137 @@
+use clap::ValueEnum;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use serde::Deserialize;
+use signal_hook::consts::{SIGHUP, SIGTERM, SIGTSTP};
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    io::{self, Write},
+    iter,
+    panic,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, AtomicI32, Ordering},
+        Arc,
+        Once
+    },
+    time::Duration,
+    vec
+};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+    Terminal, text::{Spans, Span},
+};
+use word_game::{
+    dictionary::Dictionary,
+    history::Event as HistoryEvent,
+    wordle::{self, LetterScore}
+};
+
+use crate::theme::Theme;
+
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, PartialOrd, Eq
+)]
+pub enum AppEndState {
+    Won,
+    Lost,
+    Close{forced: bool},
+    /// Stopped by a signal, like SIGTERM
+    Terminated{signal: i32}
+}
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, PartialOrd, Eq
+)]
+pub enum AppState {
+    InProgress,
+    End(AppEndState)
+}
+
+
+
+/// Order of the letters on the on-screen keyboard
+#[derive(
+    Debug, Default,
+    Clone, Copy,
+    PartialEq, Eq,
+    Deserialize, ValueEnum
+)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Azerty,
+    Qwertz,
+    Dvorak,
+    Colemak
+}
+
+impl KeyboardLayout {
+    pub fn rows(&self) -> [&'static str; 3] {
+        match self {
+            Self::Qwerty => ["qwertyuiop", "asdfghjkl", "zxcvbnm"],
+            Self::Azerty => ["azertyuiop", "qsdfghjklm", "wxcvbn"],
+            Self::Qwertz => ["qwertzuiop", "asdfghjkl", "yxcvbnm"],
+            Self::Dvorak => ["pyfgcrl", "aoeuidhtns", "qjkxbmwvz"],
+            Self::Colemak => ["qwfpgjluy", "arstdhneio", "zxcvbkm"]
+        }
+    }
+}
+
+impl Display for KeyboardLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.to_possible_value().map(|v| v.get_name().to_string()).unwrap_or_default();
+        write!(f, "{name}")
+    }
+}
+
+
+/// A key with its modifiers, written like `enter`, `ctrl+w` or `alt+backspace`
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq,
+    Deserialize
+)]
+#[serde(try_from = "String")]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers
+}
+
+impl KeyBinding {
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        self.code == key.code && self.modifiers == key.modifiers
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim().to_lowercase();
+        // A lone '+' or a binding ending with "++" is the plus key
+        let (modifiers, key) = match value.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None if value == "+" => ("", "+"),
+            None => value.rsplit_once('+').unwrap_or(("", &value))
+        };
+
+        let mut binding = Self::new(KeyCode::Null, KeyModifiers::NONE);
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            binding.modifiers |= match modifier {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("Unknown modifier '{modifier}'"))
+            };
+        }
+        binding.code = match key {
+            "enter" | "return" => KeyCode::Enter,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "space" => KeyCode::Char(' '),
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            key if key.chars().count() == 1 => KeyCode::Char(key.chars().next().unwrap()),
+            key if key.starts_with('f') && key[1..].parse::<u8>().is_ok() =>
+                KeyCode::F(key[1..].parse().unwrap()),
+            "" => return Err("Missing key after the modifiers".to_string()),
+            _ => return Err(format!("Unknown key '{key}'"))
+        };
+        Ok(binding)
+    }
+}
+
+impl TryFrom<String> for KeyBinding {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl"),
+            (KeyModifiers::ALT, "alt"),
+            (KeyModifiers::SHIFT, "shift")
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match self.code {
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Char(char) => write!(f, "{char}"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            code => write!(f, "{code:?}")
+        }
+    }
+}
+
+
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub struct KeyBindings {
+    pub submit: KeyBinding,
+    pub delete: KeyBinding,
+    pub clear: KeyBinding,
+    pub quit: KeyBinding,
+    pub hint: KeyBinding,
+    pub undo: KeyBinding,
+    /// Next step of a replay
+    pub next: KeyBinding,
+    /// Previous step of a replay
+    pub previous: KeyBinding,
+    /// Switches to the next colour theme
+    pub theme: KeyBinding
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            submit: KeyBinding::new(KeyCode::Enter, KeyModifiers::NONE),
+            delete: KeyBinding::new(KeyCode::Backspace, KeyModifiers::NONE),
+            clear: KeyBinding::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
+            quit: KeyBinding::new(KeyCode::Esc, KeyModifiers::NONE),
+            hint: KeyBinding::new(KeyCode::Tab, KeyModifiers::NONE),
+            undo: KeyBinding::new(KeyCode::Char('u'), KeyModifiers::CONTROL),
+            next: KeyBinding::new(KeyCode::Right, KeyModifiers::NONE),
+            previous: KeyBinding::new(KeyCode::Left, KeyModifiers::NONE),
+            theme: KeyBinding::new(KeyCode::Char('t'), KeyModifiers::CONTROL)
+        }
+    }
+}
+
+
+/// How the game is displayed and controlled
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub struct UiConfig {
+    pub layout: KeyboardLayout,
+    /// Colours of the tiles, keyboard, borders and messages
+    pub theme: Theme,
+    /// Whether tiles flip, shake, pop and bounce
+    pub animations: bool,
+    /// Ctrl+C always quits, whatever the bindings
+    pub keys: KeyBindings
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            layout: KeyboardLayout::default(),
+            theme: Theme::default(),
+            animations: true,
+            keys: KeyBindings::default()
+        }
+    }
+}
+
+impl UiConfig {
+    /// Switches to the theme after the current one in `themes`
+    fn next_theme(&mut self, themes: &[Theme]) {
+        let current = themes.iter().position(|theme| theme.name == self.theme.name);
+        let next = current.map_or(0, |i| (i + 1) % themes.len());
+        if let Some(theme) = themes.get(next) {
+            self.theme = theme.clone();
+        }
+    }
+}
+
+
+
+pub trait Drawable {
+    fn render<B: Backend>(&self, f: &mut Frame<B>);
+}
+
+
+/// A screen of the interface, drawn and updated until it ends
+pub trait Screen: Drawable {
+    fn handle(&mut self, event: Event);
+
+    fn state(&self) -> AppState;
+
+    /// Moves the animations one step forward, returns whether they changed what is drawn
+    fn tick(&mut self) -> bool {
+        false
+    }
+
+    /// Waits for the next event of `events` and handles it
+    fn update<E: EventSource>(&mut self, events: &mut E) {
+        if let Ok(event) = events.read() {
+            self.handle(event)
+        }
+    }
+}
+
+
+/// Where the interface reads its input from
+pub trait EventSource {
+    /// Whether an event can be read without waiting longer than `timeout`
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool>;
+
+    /// Waits for the next event
+    fn read(&mut self) -> io::Result<Event>;
+}
+
+/// Events given in advance, like scripted keys, reading past the last one fails
+impl EventSource for VecDeque<Event> {
+    fn poll(&mut self, _: Duration) -> io::Result<bool> {
+        Ok(!self.is_empty())
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        self.pop_front()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "No more events"))
+    }
+}
+
+
+/// Why the interface should stop for a while or for good
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interrupt {
+    /// Ctrl+Z or SIGTSTP
+    Suspend,
+    /// SIGTERM or SIGHUP, with the number of the signal
+    Terminate {
+        signal: i32
+    }
+}
+
+/// Events of the terminal, and the signals sent to the game while it runs
+pub struct TerminalEvents {
+    suspend: Arc<AtomicBool>,
+    /// Number of the last terminating signal, 0 if none
+    terminate: Arc<AtomicI32>
+}
+
+impl TerminalEvents {
+    pub fn new() -> io::Result<Self> {
+        let events = Self {
+            suspend: Arc::new(AtomicBool::new(false)),
+            terminate: Arc::new(AtomicI32::new(0))
+        };
+        signal_hook::flag::register(SIGTSTP, Arc::clone(&events.suspend))?;
+        for signal in [SIGTERM, SIGHUP] {
+            let terminate = Arc::clone(&events.terminate);
+            // Only stores the number, which is safe in a signal handler
+            unsafe {
+                signal_hook::low_level::register(signal, move || terminate.store(signal, Ordering::SeqCst))?;
+            }
+        }
+        Ok(events)
+    }
+
+    /// Interrupt received since the last call, termination first
+    pub fn interrupt(&self) -> Option<Interrupt> {
+        match self.terminate.swap(0, Ordering::SeqCst) {
+            0 => self.suspend.swap(false, Ordering::SeqCst).then_some(Interrupt::Suspend),
+            signal => Some(Interrupt::Terminate { signal })
+        }
+    }
+}
+
+impl EventSource for TerminalEvents {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        event::poll(timeout)
+    }
+
+    /// Ctrl+Z suspends the game like in a shell, raw mode keeps the terminal from sending SIGTSTP itself
+    fn read(&mut self) -> io::Result<Event> {
+        let event = event::read()?;
+        if let Event::Key(KeyEvent { code: KeyCode::Char('z'), modifiers: KeyModifiers::CONTROL, .. }) = event {
+            self.suspend.store(true, Ordering::SeqCst);
+        }
+        Ok(event)
+    }
+}
+
+
+
+struct LetterBoxStyle {
+    style: Style,
+    borders: Borders
+}
+impl LetterBoxStyle {
+    /// Colours of the score in `theme`, with borders telling the scores apart without colours
+    pub fn new(score: LetterScore, theme: &Theme) -> Self {
+        let (colors, borders) = match score {
+            LetterScore::Unknown => (theme.unknown, Borders::BOTTOM),
+            LetterScore::Wrong => (theme.wrong, Borders::NONE),
+            LetterScore::Present => (theme.present, Borders::LEFT | Borders::RIGHT),
+            LetterScore::Correct => (theme.correct, Borders::ALL)
+        };
+        Self {
+            style: colors.style().add_modifier(Modifier::BOLD),
+            borders
+        }
+    }
+
+    pub fn to_styles(&self) -> (Style, Borders) {
+        (self.style, self.borders)
+    }
+}
+
+
+
+/// How a letter box is drawn at a step of an animation
+#[derive(
+    Debug, Default,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub enum Effect {
+    #[default]
+    None,
+    /// Halfway through flipping, only its middle line shows
+    Squashed,
+    /// Just typed, fully outlined
+    Popped,
+    /// Moved by some columns and lines
+    Moved(i16, i16)
+}
+
+
+struct LetterBox {
+    pos: (u16, u16),
+    char: char,
+    style: (Style, Borders),
+    /// Known number of the letter in the answer, shown in the corner from 2
+    count: usize,
+    effect: Effect
+}
+impl LetterBox {
+    const SIZE_X: u16 = 5;
+    const SIZE_Y: u16 = 3;
+    const GAP_X: u16 = 2;
+    const GAP_Y: u16 = 1;
+
+    pub fn new(pos: (u16, u16), char: char, score: LetterScore, theme: &Theme) -> Self {
+        Self {
+            pos,
+            char,
+            style: LetterBoxStyle::new(score, theme).to_styles(),
+            count: 0,
+            effect: Effect::None
+        }
+    }
+
+    pub fn with_count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn with_effect(mut self, effect: Effect) -> Self {
+        self.effect = effect;
+        self
+    }
+
+    pub fn compute_new_pos(pos: (u16, u16), offset: (u16, u16)) -> (u16, u16) {
+        let pos_x =
+            pos.0
+            + offset.0 * (Self::SIZE_X + Self::GAP_X);
+        let pos_y =
+            pos.1
+            + offset.1 * (Self::SIZE_Y + Self::GAP_Y);
+        (pos_x, pos_y)
+    }
+    pub fn compute_size(count: (u16, u16)) -> (u16, u16) {
+        let size_x =
+            if count.0 > 0 {
+                (count.0 - 1) * (Self::SIZE_X + Self::GAP_X) + Self::SIZE_X
+            }
+            else {
+                0
+            };
+        let size_y =
+            if count.1 > 0 {
+                (count.1 - 1) * (Self::SIZE_Y + Self::GAP_Y) + Self::SIZE_Y
+            }
+            else {
+                0
+            };
+        (size_x, size_y)
+    }
+}
+impl Drawable for LetterBox {
+    fn render<B: Backend>(&self, f: &mut Frame<B>) {
+        let (x, y) = match self.effect {
+            Effect::Moved(dx, dy) => (self.pos.0.saturating_add_signed(dx), self.pos.1.saturating_add_signed(dy)),
+            _ => self.pos
+        };
+        let borders = match self.effect {
+            Effect::Popped => Borders::ALL,
+            _ => self.style.1
+        };
+        // Moved boxes may go past the edges
+        let area = f.size();
+        let clip = |rect: Rect| rect.intersection(area);
+
+        let block = Block::default()
+            .style(self.style.0)
+            .borders(borders)
+            .border_type(BorderType::Thick);
+        let block_area = if self.effect == Effect::Squashed {
+            Rect { x, y: y + (Self::SIZE_Y - 1) / 2, width: Self::SIZE_X, height: 1 }
+        }
+        else {
+            Rect { x, y, width: Self::SIZE_X, height: Self::SIZE_Y }
+        };
+        f.render_widget(
+            if self.effect == Effect::Squashed { Block::default().style(self.style.0) } else { block },
+            clip(block_area)
+        );
+        let paragraph = Paragraph::new(self.char.to_uppercase().to_string())
+            .style(self.style.0);
+        f.render_widget(
+            paragraph,
+            clip(Rect {
+                x: x + (Self::SIZE_X - 1) / 2,
+                y: y + (Self::SIZE_Y - 1) / 2,
+                width: 1,
+                height: 1
+            })
+        );
+        if self.count > 1 && self.effect != Effect::Squashed {
+            let count = Paragraph::new(self.count.to_string())
+                .style(self.style.0.remove_modifier(Modifier::BOLD));
+            f.render_widget(
+                count,
+                clip(Rect {
+                    x: x + Self::SIZE_X - 1,
+                    y,
+                    width: 1,
+                    height: 1
+                })
+            )
+        }
+    }
+}
+
+
+pub struct LetterBoxWord<'a> {
+    pub pos: (u16, u16),
+    pub word: &'a str,
+    pub scores: &'a [wordle::LetterScore],
+    /// Counts shown on the letters, none if empty
+    pub counts: &'a [usize],
+    /// Effects of the animations on the letters, none if empty
+    pub effects: &'a [Effect],
+    pub theme: &'a Theme
+}
+impl Drawable for LetterBoxWord<'_> {
+    fn render<B: Backend>(&self, f: &mut Frame<B>) {
+        for (i, (char, score)) in self.word.chars().zip(self.scores).enumerate() {
+            LetterBox::new(
+                LetterBox::compute_new_pos(self.pos, (i as u16, 0)),
+                char,
+                *score,
+                self.theme
+            )
+                .with_count(self.counts.get(i).copied().unwrap_or_default())
+                .with_effect(self.effects.get(i).copied().unwrap_or_default())
+                .render(f);
+        }
+    }
+}
+
+
+
+
+/// Length of the animations, in ticks of the interface
+const FLIP_TICKS: u32 = 4;
+/// Between two letters starting to flip
+const FLIP_DELAY: u32 = 2;
+const POP_TICKS: u32 = 2;
+const BOUNCE_TICKS: u32 = 3;
+/// Between two letters starting to bounce
+const BOUNCE_DELAY: u32 = 1;
+/// Columns the row is moved by at each tick of a shake
+const SHAKE: [i16; 8] = [2, -2, 2, -2, 1, -1, 1, 0];
+
+/// Ticks until every letter of a word is flipped
+fn flip_ticks(len: usize) -> u32 {
+    len.saturating_sub(1) as u32 * FLIP_DELAY + FLIP_TICKS
+}
+
+fn bounce_ticks(len: usize) -> u32 {
+    len.saturating_sub(1) as u32 * BOUNCE_DELAY + BOUNCE_TICKS
+}
+
+/// A word being typed padded to `len` letters, with its scores
+fn typed_row(word: &str, len: usize) -> (String, Vec<LetterScore>) {
+    let padded: String = (0..len).map(|i| word.chars().nth(i).unwrap_or(' ')).collect();
+    let scores = padded
+        .chars()
+        .map(|c| if c.is_alphabetic() { LetterScore::Unknown } else { LetterScore::Wrong })
+        .collect();
+    (padded, scores)
+}
+
+/// Animations of the board, each with the tick it started at
+#[derive(Debug, Default, Clone)]
+struct Animations {
+    tick: u32,
+    /// Last tick of the animations started so far
+    until: u32,
+    /// Row of the guess revealing its scores
+    flip: Option<(usize, u32)>,
+    /// Refused word, shaken on the row of the current guess
+    shake: Option<(String, u32)>,
+    /// Position of the letter just typed
+    pop: Option<(usize, u32)>,
+    /// Row of the winning guess, bouncing once flipped
+    bounce: Option<(usize, u32)>
+}
+
+
+pub struct App {
+    game: wordle::WordleGame,
+    guess: String,
+    error: String,
+    /// Shown instead of the error when there is none
+    notice: String,
+    state: AppState,
+    config: UiConfig,
+    /// Themes the theme key switches between
+    themes: Vec<Theme>,
+    animations: Animations
+}
+
+impl Drawable for App {
+    fn render<B: Backend>(&self, f: &mut Frame<B>) {
+        let size = f.size();
+
+        let guess_empty_scores = self.game.guess_empty();
+        let word_length = guess_empty_scores.len();
+
+        // Each keyboard row is shifted right of the previous one
+        let keyboard_rows = self.config.layout.rows();
+        let keyboard_width = keyboard_rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| LetterBox::compute_size((row.len() as u16, 0)).0 + i as u16 * (LetterBox::SIZE_X - 1))
+            .max()
+            .unwrap_or_default();
+
+        // Every guess, the current one, the error line and the keyboard rows
+        let attempts = self.game.tries().len() + self.game.lives();
+        let minimum_size = LetterBox::compute_size((
+            word_length as u16,
+            (attempts + 1 + 1 + 3) as u16
+        ));
+        let minimum_size = (minimum_size.0.max(keyboard_width) + 4, minimum_size.1 + 4);
+
+        // Main box
+        let theme = &self.config.theme;
+        let main_box = Block::default()
+            .style(theme.text.style())
+            .borders(Borders::ALL)
+            .border_style(theme.border.style())
+            .border_type(BorderType::Rounded)
+            .title(Span::styled("RUSTLE", theme.border.style()))
+            .title_alignment(Alignment::Center);
+        f.render_widget(main_box, size);
+
+        if size.width >= minimum_size.0 && size.height >= minimum_size.1 {
+            // Enough size to draw the game
+
+            // Tries
+            // Current guess
+            let (guess_padded, guess_scores) = typed_row(&self.guess, word_length);
+            // Future guesses
+            let guess_empty = str::repeat(" ", word_length);
+            // All guesses
+            let tries: Vec<(String, Vec<wordle::LetterScore>)> = self.game
+                .tries()
+                .iter()
+                .map(|(word, pattern)| (word.clone(), pattern.to_vec()))
+                .collect();
+            let current = (guess_padded, guess_scores);
+            let future = (guess_empty, guess_empty_scores);
+            let all_guesses =
+                tries.iter()
+                    .chain(iter::once(&current))
+                    .chain(iter::repeat_n(&future, self.game.lives()));
+            let guess_start_x = size.width.saturating_sub(LetterBox::compute_size((word_length as u16, 0)).0) / 2;
+            for (i, (word, scores)) in all_guesses.enumerate() {
+                let (word, scores, effects) = self.animate_row(i, word, scores);
+                LetterBoxWord {
+                    pos: LetterBox::compute_new_pos((guess_start_x, 2), (0, i as u16)),
+                    word: &word,
+                    scores: &scores,
+                    counts: &[],
+                    effects: &effects,
+                    theme: &self.config.theme
+                }.render(f);
+            }
+
+            // Keyboard
+            let keyboard_size = (keyboard_width, LetterBox::compute_size((0, 3)).1);
+            for (i, row) in keyboard_rows.iter().enumerate() {
+                let scores = self.game.known_guesses(row);
+                let counts: Vec<usize> = row
+                    .chars()
+                    .map(|c| self.game.knowledge().letter(c).min())
+                    .collect();
+                let pos_x =
+                    (size.width - keyboard_size.0) / 2
+                    + i as u16 * (LetterBox::SIZE_X - 1);
+                let pos_y =
+                    size.height - 2
+                    - LetterBox::compute_size((0, (3 - i) as u16)).1;
+
+                LetterBoxWord {
+                    pos: (pos_x, pos_y),
+                    word: row,
+                    scores: &scores,
+                    counts: &counts,
+                    effects: &[],
+                    theme: &self.config.theme
+                }.render(f);
+            }
+
+            // Error message, or notice if there is no error
+            let error = if self.error.is_empty() {
+                Paragraph::new(self.notice.clone())
+            }
+            else {
+                Paragraph::new(self.error.clone())
+                    .style(theme.error.style())
+            }.alignment(Alignment::Center);
+            f.render_widget(error, Rect {
+                x: 2,
+                y: size.height - 2 - keyboard_size.1 - 2,
+                width: size.width - 2,
+                height: 2
+            });
+        }
+        else {
+            // Error message box
+            let style_x = if size.width < minimum_size.0 { theme.size_short.style() } else { theme.size_enough.style() };
+            let style_y = if size.height < minimum_size.1 { theme.size_short.style() } else { theme.size_enough.style() };
+
+            let text = vec![
+                Spans::from(Span::raw("Terminal window is too small")),
+                Spans::from(vec![
+                    Span::raw("Width = "),
+                    Span::styled(format!("{}", size.width), style_x),
+                    Span::raw(format!(" (needed {})", minimum_size.0)),
+                ]),
+                Spans::from(vec![
+                    Span::raw("Height = "),
+                    Span::styled(format!("{}", size.height), style_y),
+                    Span::raw(format!(" (needed {})", minimum_size.1)),
+                ]),
+            ];
+
+            let paragraph = Paragraph::new(text)
+                .style(theme.too_small.style())
+                .alignment(Alignment::Center);
+            f.render_widget(paragraph, size);
+        }
+    }
+}
+
+impl Screen for App {
+    fn handle(&mut self, event: Event) {
+        if let Event::Key(key) = event {
+            if key.kind == KeyEventKind::Press {
+                let keys = self.config.keys;
+                if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('c') {
+                    self.state = AppState::End(AppEndState::Close { forced: true })
+                }
+                else if keys.submit.matches(&key) {
+                    self.submit_input()
+                }
+                else if keys.delete.matches(&key) {
+                    self.remove_from_input()
+                }
+                else if keys.clear.matches(&key) {
+                    self.guess.clear()
+                }
+                else if keys.quit.matches(&key) {
+                    self.state = AppState::End(AppEndState::Close { forced: false })
+                }
+                else if keys.hint.matches(&key) {
+                    self.use_hint()
+                }
+                else if keys.undo.matches(&key) {
+                    self.undo()
+                }
+                else if keys.theme.matches(&key) {
+                    self.config.next_theme(&self.themes);
+                    self.notice = format!("Theme {}", self.config.theme.name);
+                }
+                else if let KeyCode::Char(char) = key.code {
+                    // Unbound shortcuts like Ctrl+Z do not type
+                    if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+                        self.add_to_input(char)
+                    }
+                }
+            }
+        }
+    }
+
+    fn state(&self) -> AppState {
+        self.state
+    }
+
+    fn tick(&mut self) -> bool {
+        self.animations.tick += 1;
+        self.animations.tick <= self.animations.until
+    }
+}
+
+impl App {
+    pub fn new(game: wordle::WordleGame) -> Self {
+        Self {
+            game,
+            guess: "".to_string(),
+            error: "".to_string(),
+            notice: "".to_string(),
+            state: AppState::InProgress,
+            config: UiConfig::default(),
+            themes: Theme::default().cycle(),
+            animations: Animations::default()
+        }
+    }
+
+    pub fn with_config(mut self, config: UiConfig) -> Self {
+        self.themes = config.theme.cycle();
+        self.config = config;
+        self
+    }
+
+    pub fn game(&self) -> &wordle::WordleGame {
+        &self.game
+    }
+
+    fn add_to_input(&mut self, char: char) {
+        if self.guess.len() < self.game.guess_empty().len() {
+            self.guess.push(char);
+            self.animations.shake = None;
+            self.animate(|a| &mut a.pop, self.guess.chars().count() - 1, POP_TICKS);
+        }
+    }
+
+    fn remove_from_input(&mut self) {
+        self.guess.pop();
+    }
+
+    fn submit_input(&mut self) {
+        if self.state == AppState::InProgress {
+            let score = self.game.guess(self.guess.as_str());
+            match score {
+                Ok(_) => {
+                    let row = self.game.tries().len() - 1;
+                    let flip_ticks = flip_ticks(self.game.guess_empty().len());
+                    self.animations.shake = None;
+                    self.animate(|a| &mut a.flip, row, flip_ticks);
+                    if self.game.is_won() {
+                        let bounce_ticks = flip_ticks + bounce_ticks(self.game.guess_empty().len());
+                        self.animate(|a| &mut a.bounce, row, bounce_ticks);
+                    }
+                    /*
+                    if let Some(_) = score.iter().filter(|&s| *s != wordle::LetterScore::Correct).next() {
+                        self.state = AppState::End(AppEndState::Won);
+                    }
+                    else if self.game.lives() == 0 {
+                        self.state = AppState::End(AppEndState::Lost);
+                    }
+                    */
+                    self.guess.clear();
+                    self.error.clear();
+                    self.notice.clear();
+                }
+                Err(error) => {
+                    if let wordle::GameError::InvalidWord(_) = error {
+                        let shaken = self.guess.clone();
+                        self.animate(|a| &mut a.shake, shaken, SHAKE.len() as u32);
+                    }
+                    self.guess.clear();
+                    self.error = error.to_string();
+                }
+            }
+        }
+    }
+
+    /// Starts the animation in `field` at this tick, for `ticks` ticks
+    fn animate<T>(&mut self, field: impl Fn(&mut Animations) -> &mut Option<(T, u32)>, value: T, ticks: u32) {
+        if self.config.animations {
+            let tick = self.animations.tick;
+            *field(&mut self.animations) = Some((value, tick));
+            self.animations.until = self.animations.until.max(tick + ticks);
+        }
+    }
+
+    /// Row `row` of the board as drawn at this tick of the animations
+    fn animate_row(&self, row: usize, word: &str, scores: &[LetterScore]) -> (String, Vec<LetterScore>, Vec<Effect>) {
+        let animations = &self.animations;
+        // Ticks since each letter started to be animated, `None` if it did not yet
+        let elapsed = |start: u32, pos: usize, delay: u32| animations.tick.checked_sub(start + pos as u32 * delay);
+
+        let mut word = word.to_string();
+        let mut scores = scores.to_vec();
+        let mut effects = vec![Effect::None; scores.len()];
+        if let Some((flipped, start)) = animations.flip {
+            if flipped == row {
+                for (pos, (score, effect)) in scores.iter_mut().zip(&mut effects).enumerate() {
+                    match elapsed(start, pos, FLIP_DELAY) {
+                        Some(ticks) if ticks >= FLIP_TICKS => (),
+                        // Turned past the middle, showing its score
+                        Some(ticks) if ticks >= FLIP_TICKS / 2 => *effect = Effect::Squashed,
+                        Some(_) => (*score, *effect) = (LetterScore::Unknown, Effect::Squashed),
+                        None => *score = LetterScore::Unknown
+                    }
+                }
+            }
+        }
+        if let Some((bounced, start)) = animations.bounce {
+            if bounced == row {
+                // Once every letter is flipped
+                let start = start + flip_ticks(scores.len());
+                for (pos, effect) in effects.iter_mut().enumerate() {
+                    if elapsed(start, pos, BOUNCE_DELAY).is_some_and(|ticks| ticks < BOUNCE_TICKS) {
+                        *effect = Effect::Moved(0, -1);
+                    }
+                }
+            }
+        }
+        if row == self.game.tries().len() {
+            if let Some((shaken, start)) = &animations.shake {
+                if let Some(dx) = SHAKE.get((animations.tick - start) as usize) {
+                    (word, scores) = typed_row(shaken, scores.len());
+                    effects = vec![Effect::Moved(*dx, 0); scores.len()];
+                }
+            }
+            if let Some((typed, start)) = animations.pop {
+                if animations.tick - start < POP_TICKS && typed < effects.len() {
+                    effects[typed] = Effect::Popped;
+                }
+            }
+        }
+        (word, scores, effects)
+    }
+
+    fn use_hint(&mut self) {
+        match self.game.hint() {
+            Ok((letter, pos)) => {
+                self.error.clear();
+                self.notice = format!("Letter {} is at position {}", letter.to_uppercase(), pos + 1);
+            },
+            Err(error) =>
+                self.error = error.to_string()
+        }
+    }
+
+    fn undo(&mut self) {
+        if self.game.undo() {
+            self.animations = Animations { tick: self.animations.tick, ..Default::default() };
+            self.error.clear();
+            self.notice.clear();
+        }
+        else if self.game.is_over() {
+            self.error = "The game is over".to_string();
+        }
+        else {
+            self.error = "There is nothing to undo".to_string();
+        }
+    }
+}
+
+
+/// Steps through the events of a recorded game on the board
+pub struct Replay {
+    words: Dictionary,
+    history: Vec<HistoryEvent>,
+    step: usize,
+    /// The board after the event at `step`, only rebuilt when the step changes
+    app: App,
+    state: AppState,
+    config: UiConfig,
+    themes: Vec<Theme>
+}
+
+impl Drawable for Replay {
+    fn render<B: Backend>(&self, f: &mut Frame<B>) {
+        self.app.render(f);
+    }
+}
+
+impl Screen for Replay {
+    fn handle(&mut self, event: Event) {
+        if let Event::Key(key) = event {
+            if key.kind == KeyEventKind::Press {
+                let keys = self.config.keys;
+                if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('c') {
+                    self.state = AppState::End(AppEndState::Close { forced: true })
+                }
+                else if keys.next.matches(&key) || keys.submit.matches(&key) {
+                    self.show((self.step + 1).min(self.history.len() - 1))
+                }
+                else if keys.previous.matches(&key) || keys.delete.matches(&key) {
+                    self.show(self.step.saturating_sub(1))
+                }
+                else if keys.quit.matches(&key) {
+                    self.state = AppState::End(AppEndState::Close { forced: false })
+                }
+                else if keys.theme.matches(&key) {
+                    self.config.next_theme(&self.themes);
+                    self.app.config = self.config.clone();
+                }
+            }
+        }
+    }
+
+    fn state(&self) -> AppState {
+        self.state
+    }
+}
+
+impl Replay {
+    /// Replays the events of `game` one by one, it should be rebuilt from them
+    pub fn new(game: &wordle::WordleGame) -> Self {
+        let mut replay = Self {
+            words: game.words().clone(),
+            history: game.history().to_vec(),
+            step: 0,
+            app: App::new(game.clone()),
+            state: AppState::InProgress,
+            config: UiConfig::default(),
+            themes: Theme::default().cycle()
+        };
+        replay.rebuild();
+        replay
+    }
+
+    pub fn with_config(mut self, config: UiConfig) -> Self {
+        self.themes = config.theme.cycle();
+        self.config = config;
+        self.rebuild();
+        self
+    }
+
+    /// Shows the board after the event at `step`
+    fn show(&mut self, step: usize) {
+        if step != self.step {
+            self.step = step;
+            self.rebuild();
+        }
+    }
+
+    fn rebuild(&mut self) {
+        // Replaying a prefix of the events of a valid game cannot fail
+        if let Ok(game) = wordle::WordleGame::from_history(self.words.clone(), &self.history[..=self.step]) {
+            self.app = App::new(game).with_config(self.config.clone());
+        }
+        self.app.notice = format!(
+            "{}/{}: {}  ({} previous, {} next)",
+            self.step + 1,
+            self.history.len(),
+            self.history[self.step],
+            self.config.keys.previous,
+            self.config.keys.next
+        );
+    }
+}
+
+
+pub fn start_ui<B>(backend: B) -> Result<Terminal<B>, io::Error>
+    where B: Backend
+{
+    // A panic would leave the terminal in raw mode, unusable
+    static PANIC_HOOK: Once = Once::new();
+    PANIC_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let _ = restore_terminal();
+            hook(info);
+        }));
+    });
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(
+        stdout,
+        EnterAlternateScreen
+    )?;
+    let terminal = Terminal::new(backend)?;
+
+    Ok(terminal)
+}
+
+pub fn end_ui<B: Backend + Write>(mut terminal: Terminal<B>) -> Result<(), io::Error> {
+    disable_raw_mode()?;
+    let backend = terminal.backend_mut();
+    execute!(
+        backend,
+        LeaveAlternateScreen
+    )?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+/// Gives the terminal back to the shell until the game is resumed with SIGCONT, then redraws everything
+pub fn suspend_ui<B: Backend + Write>(terminal: &mut Terminal<B>) -> Result<(), io::Error> {
+    restore_terminal()?;
+    signal_hook::low_level::emulate_default_handler(SIGTSTP)?;
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen
+    )?;
+    terminal.clear()
+}
+
+/// Leaves the interface without the terminal, like in a panic hook
+pub fn restore_terminal() -> Result<(), io::Error> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        cursor::Show
+    )
+}
@@ -0,0 +1,316 @@
+//! Sets of canonical words that guesses are looked up in.
+
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    fmt::Display,
+    str::FromStr
+};
+
+use crate::words::canonicalize;
+
+
+
+type Words = Vec<String>;
+
+/// Bits used by a letter in a packed word
+const LETTER_BITS: u32 = 5;
+/// Longest word that fits in a packed word
+const PACKED_MAX_LEN: usize = (u64::BITS / LETTER_BITS) as usize;
+
+
+include!(concat!(env!("OUT_DIR"), "/word_list.rs"));
+
+
+/// Canonical and deduplicated words, sorted by length and then alphabetically.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+pub struct Dictionary {
+    storage: Storage
+}
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq
+)]
+enum Storage {
+    /// Words made of at most 12 ASCII lowercase letters are looked up as packed integers without allocating
+    Owned {
+        words: Words,
+        packed: HashSet<u64>,
+        unpacked: HashSet<String>
+    },
+    /// Groups of words of the same length generated at build time, looked up with a binary search
+    Bundled(&'static [(usize, &'static str)])
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self {
+            storage: Storage::Owned {
+                words: Words::new(),
+                packed: HashSet::new(),
+                unpacked: HashSet::new()
+            }
+        }
+    }
+}
+
+impl Dictionary {
+    /// Canonicalizes and deduplicates `words`, with what had to be changed
+    pub fn new<I>(words: I) -> (Self, Normalization)
+        where I: IntoIterator<Item = String>
+    {
+        let mut normalization = Normalization::default();
+        let mut canonical_words = Words::new();
+        let mut packed = HashSet::new();
+        let mut unpacked = HashSet::new();
+        for word in words {
+            let canonical = canonicalize(&word);
+            let inserted = match pack(&canonical) {
+                Some(p) =>
+                    packed.insert(p),
+                None =>
+                    unpacked.insert(canonical.clone())
+            };
+            if !inserted {
+                normalization.duplicates.push(canonical);
+                continue;
+            }
+            if canonical != word {
+                normalization.changed.push((word, canonical.clone()));
+            }
+            canonical_words.push(canonical);
+        }
+        canonical_words.sort_by(|a, b| a.chars().count().cmp(&b.chars().count()).then_with(|| a.cmp(b)));
+
+        let dictionary = Self {
+            storage: Storage::Owned {
+                words: canonical_words,
+                packed,
+                unpacked
+            }
+        };
+        (dictionary, normalization)
+    }
+
+    /// The word list bundled with the game, validated and sorted at build time
+    pub fn bundled() -> Self {
+        Self {
+            storage: Storage::Bundled(BUNDLED_WORDS)
+        }
+    }
+
+    /// Whether the canonical `word` is in the dictionary
+    pub fn contains(&self, word: &str) -> bool {
+        match &self.storage {
+            Storage::Owned { packed, unpacked, .. } =>
+                match pack(word) {
+                    Some(p) =>
+                        packed.contains(&p),
+                    None =>
+                        unpacked.contains(word)
+                },
+            Storage::Bundled(groups) =>
+                match groups.iter().find(|(length, _)| *length == word.len()) {
+                    Some(&(length, group)) => {
+                        let count = group.len() / length;
+                        let (mut low, mut high) = (0, count);
+                        while low < high {
+                            let middle = (low + high) / 2;
+                            match group[middle * length..(middle + 1) * length].cmp(word) {
+                                Ordering::Equal =>
+                                    return true,
+                                Ordering::Less =>
+                                    low = middle + 1,
+                                Ordering::Greater =>
+                                    high = middle
+                            }
+                        }
+                        false
+                    },
+                    None =>
+                        false
+                }
+        }
+    }
+
+    /// Number of words
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Owned { words, .. } =>
+                words.len(),
+            Storage::Bundled(groups) =>
+                groups.iter().map(|(length, group)| group.len() / length).sum()
+        }
+    }
+
+    /// Whether there are no words
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every word, by length and then alphabetically
+    pub fn iter(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        match &self.storage {
+            Storage::Owned { words, .. } =>
+                Box::new(words.iter().map(String::as_str)),
+            Storage::Bundled(groups) =>
+                Box::new(
+                    groups
+                        .iter()
+                        .flat_map(|&(length, group)| (0..group.len() / length).map(move |i| &group[i * length..(i + 1) * length]))
+                )
+        }
+    }
+}
+
+impl Dictionary {
+    /// Words of this dictionary for which `predicate` is true
+    pub fn filter<P>(&self, predicate: P) -> Dictionary
+        where P: Fn(&str) -> bool
+    {
+        self.iter()
+            .filter(|word| predicate(word))
+            .map(str::to_string)
+            .collect::<Words>()
+            .into()
+    }
+
+    /// Words of this dictionary that are not in `excluded`
+    pub fn without(&self, excluded: &Dictionary) -> Dictionary {
+        self.filter(|word| !excluded.contains(word))
+    }
+
+    /// Identifies the words of this dictionary without storing them
+    pub fn fingerprint(&self) -> Fingerprint {
+        // FNV-1a, stable across builds and platforms unlike the hasher of the standard library
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for word in self.iter() {
+            for byte in word.bytes().chain([b'\n']) {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3);
+            }
+        }
+        Fingerprint(hash)
+    }
+}
+
+impl From<Words> for Dictionary {
+    fn from(words: Words) -> Self {
+        Self::new(words).0
+    }
+}
+
+/// Serialized as the list of its words
+#[cfg(feature = "serde")]
+impl serde::Serialize for Dictionary {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Dictionary {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <Words as serde::Deserialize>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+
+/// Identifies a set of words, the same words always give the same fingerprint.
+/// Written as 16 hexadecimal digits
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq, Hash
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub struct Fingerprint(u64);
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.len() != 16 || !value.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(format!("Invalid word list fingerprint '{value}'"));
+        }
+        Ok(Self(u64::from_str_radix(value, 16).unwrap()))
+    }
+}
+
+impl From<Fingerprint> for String {
+    fn from(fingerprint: Fingerprint) -> Self {
+        fingerprint.to_string()
+    }
+}
+
+impl TryFrom<String> for Fingerprint {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+
+/// Packs a word of ASCII lowercase letters into an integer, 5 bits per letter starting from `a = 1`.
+/// Returns `None` if the word has other characters or is too long.
+pub fn pack(word: &str) -> Option<u64> {
+    if word.len() > PACKED_MAX_LEN {
+        return None;
+    }
+    word.bytes().try_fold(0, |packed, byte| {
+        if byte.is_ascii_lowercase() {
+            Some(packed << LETTER_BITS | (byte - b'a' + 1) as u64)
+        }
+        else {
+            None
+        }
+    })
+}
+
+
+
+/// What was changed in the words to make a [`Dictionary`]
+#[derive(Debug, Default, Clone)]
+pub struct Normalization {
+    /// Words rewritten to their canonical form, as `(original, canonical)`
+    pub changed: Vec<(String, String)>,
+    /// Canonical words removed because they were already in the dictionary
+    pub duplicates: Words
+}
+
+impl Normalization {
+    /// Whether every word was already canonical and unique
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.duplicates.is_empty()
+    }
+}
+
+impl Display for Normalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f, "Normalized {} words and removed {} duplicates",
+            self.changed.len(),
+            self.duplicates.len()
+        )?;
+        for (original, canonical) in &self.changed {
+            write!(f, "\n- {original:?} -> '{canonical}'")?;
+        }
+        for duplicate in &self.duplicates {
+            write!(f, "\n- '{duplicate}' is a duplicate")?;
+        }
+        Ok(())
+    }
+}
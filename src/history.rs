@@ -0,0 +1,247 @@
+//! Games as ordered logs of events, and the replay file format.
+//!
+//! A replay file has a version header, the [fingerprint](crate::dictionary::Fingerprint) of the words
+//! the game was played with and one event per line, blank lines and lines starting with `#` are skipped:
+//!
+//! ```text
+//! #! version: 1
+//! #! words: 6e2c1b3fbd1d8a07
+//! created those attempts=6 hard=false seed=42
+//! guessed tiles g..yy
+//! hint h 2
+//! guessed those ggggg
+//! ended won
+//! ```
+
+use std::{
+    fmt::Display,
+    fs,
+    str::FromStr
+};
+
+use crate::dictionary::Fingerprint;
+use crate::pattern::Pattern;
+use crate::wordle::GameError;
+
+
+
+/// Version of the replay file format written by [`to_text`]
+pub const VERSION: u32 = 1;
+
+
+/// Something that happened in a game, a game is rebuilt by replaying its events in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Event {
+    /// Always the first event
+    Created {
+        /// Word to guess
+        answer: String,
+        /// Guesses allowed at the start
+        attempts: usize,
+        /// Whether guesses must use every revealed hint
+        hard: bool,
+        /// Seed the answer was picked with, `None` if it was given
+        seed: Option<u64>
+    },
+    /// A guess was scored
+    Guessed {
+        /// Canonical guessed word
+        word: String,
+        /// Score of each letter of the guess
+        pattern: Pattern
+    },
+    /// The letter of the answer at `pos` was revealed
+    HintUsed {
+        /// Revealed letter
+        letter: char,
+        /// 0-based position in the answer
+        pos: usize
+    },
+    /// The game was won or there were no guesses left, games left unfinished have no end
+    Ended {
+        /// Whether the last guess was the answer
+        won: bool
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Created { answer, attempts, hard, seed } => {
+                write!(f, "created {answer} attempts={attempts} hard={hard}")?;
+                match seed {
+                    Some(seed) => write!(f, " seed={seed}"),
+                    None => Ok(())
+                }
+            },
+            Self::Guessed { word, pattern } => write!(f, "guessed {word} {pattern}"),
+            // Positions are 1-based like in the messages of the game
+            Self::HintUsed { letter, pos } => write!(f, "hint {letter} {}", pos + 1),
+            Self::Ended { won: true } => write!(f, "ended won"),
+            Self::Ended { won: false } => write!(f, "ended lost")
+        }
+    }
+}
+
+impl FromStr for Event {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["created", answer, options @ ..] => {
+                let mut event = Self::Created {
+                    answer: answer.to_string(),
+                    attempts: answer.chars().count(),
+                    hard: false,
+                    seed: None
+                };
+                if let Self::Created { attempts, hard, seed, .. } = &mut event {
+                    for option in options {
+                        match option.split_once('=') {
+                            Some(("attempts", value)) =>
+                                *attempts = value.parse().map_err(|_| format!("Invalid attempts '{value}'"))?,
+                            Some(("hard", value)) =>
+                                *hard = value.parse().map_err(|_| format!("Invalid hard mode '{value}'"))?,
+                            Some(("seed", value)) =>
+                                *seed = Some(value.parse().map_err(|_| format!("Invalid seed '{value}'"))?),
+                            _ =>
+                                return Err(format!("Unknown option '{option}'"))
+                        }
+                    }
+                }
+                Ok(event)
+            },
+            ["guessed", word, pattern] if word.chars().count() == pattern.chars().count() =>
+                Ok(Self::Guessed { word: word.to_string(), pattern: pattern.parse()? }),
+            ["hint", letter, pos] if letter.chars().count() == 1 => {
+                let pos = pos.parse::<usize>()
+                    .ok()
+                    .filter(|pos| *pos > 0)
+                    .ok_or_else(|| format!("Invalid position '{pos}'"))?;
+                Ok(Self::HintUsed { letter: letter.chars().next().unwrap(), pos: pos - 1 })
+            },
+            ["ended", "won"] => Ok(Self::Ended { won: true }),
+            ["ended", "lost"] => Ok(Self::Ended { won: false }),
+            _ => Err(format!("Unknown event '{line}'"))
+        }
+    }
+}
+
+
+/// The events of a game with the words it was played with, as stored in replay files and serialized games
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Record {
+    /// Fingerprint of the words, `None` in replay files written without it
+    pub words: Option<Fingerprint>,
+    /// Events in order, starting with the creation of the game
+    pub events: Vec<Event>
+}
+
+
+/// Why events cannot be read or replayed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    /// The replay file cannot be read
+    NoFile,
+    /// The replay file was written by a newer version of the format
+    UnsupportedVersion {
+        /// Version written in the file
+        version: String
+    },
+    /// A line of the replay file is not an event
+    InvalidEvent {
+        /// 1-based line number
+        line: usize,
+        /// Why the line cannot be parsed
+        reason: String
+    },
+    /// The first event does not create the game, or another one does
+    MisplacedCreation,
+    /// The game was played with other words
+    OtherWords {
+        /// Fingerprint of the words of the game
+        expected: Fingerprint,
+        /// Fingerprint of the words it is replayed with
+        found: Fingerprint
+    },
+    /// Replaying an event gave another result than the recorded one
+    Mismatch {
+        /// Recorded event
+        event: Event
+    },
+    /// The game of the first event cannot be created
+    Game(GameError)
+}
+
+impl Display for HistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoFile => write!(f, "File cannot be read/does not exist"),
+            Self::UnsupportedVersion { version } => write!(f, "Replay version {version} is not supported"),
+            Self::InvalidEvent { line, reason } => write!(f, "Line {line}: {reason}"),
+            Self::MisplacedCreation => write!(f, "A game should start with its creation, and only once"),
+            Self::OtherWords { expected, found } =>
+                write!(f, "The game was played with other words ({expected}) than the current ones ({found})"),
+            Self::Mismatch { event } => write!(f, "Replaying '{event}' gives another result"),
+            Self::Game(error) => write!(f, "{error}")
+        }
+    }
+}
+
+impl From<GameError> for HistoryError {
+    fn from(error: GameError) -> Self {
+        Self::Game(error)
+    }
+}
+
+
+
+/// Writes the record in the replay file format
+pub fn to_text(record: &Record) -> String {
+    let mut text = format!("#! version: {VERSION}\n");
+    if let Some(words) = record.words {
+        text += &format!("#! words: {words}\n");
+    }
+    for event in &record.events {
+        text += &format!("{event}\n");
+    }
+    text
+}
+
+/// Reads the record of a replay file
+pub fn parse(content: &str) -> Result<Record, HistoryError> {
+    let mut record = Record { words: None, events: Vec::new() };
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if let Some(version) = line.strip_prefix("#! version:") {
+            let version = version.trim();
+            if version.parse::<u32>().map_or(true, |version| version > VERSION) {
+                return Err(HistoryError::UnsupportedVersion { version: version.to_string() });
+            }
+        }
+        else if let Some(words) = line.strip_prefix("#! words:") {
+            let words = words
+                .trim()
+                .parse()
+                .map_err(|reason| HistoryError::InvalidEvent { line: i + 1, reason })?;
+            record.words = Some(words);
+        }
+        else if !line.is_empty() && !line.starts_with('#') {
+            let event = line
+                .parse()
+                .map_err(|reason| HistoryError::InvalidEvent { line: i + 1, reason })?;
+            record.events.push(event);
+        }
+    }
+    Ok(record)
+}
+
+/// Reads the record of the replay file at `path`
+pub fn read_from(path: &str) -> Result<Record, HistoryError> {
+    let content = fs::read_to_string(path).map_err(|_| HistoryError::NoFile)?;
+    parse(&content)
+}
@@ -0,0 +1,198 @@
+//! What the previous guesses tell about the answer.
+
+use std::collections::BTreeMap;
+
+use crate::pattern::Pattern;
+use crate::wordle::{InvalidWord, LetterScore};
+
+
+
+/// What is known about a letter: where it is, where it is not and how many times it appears
+#[derive(
+    Debug, Default,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+pub struct LetterKnowledge {
+    /// Positions known to hold the letter, one bit per position
+    correct: u32,
+    /// Positions known not to hold the letter, one bit per position
+    excluded: u32,
+    min: usize,
+    max: Option<usize>
+}
+
+impl LetterKnowledge {
+    /// The answer has at least this many of the letter
+    pub fn min(&self) -> usize {
+        self.min
+    }
+
+    /// The answer has at most this many of the letter, `None` if unknown
+    pub fn max(&self) -> Option<usize> {
+        self.max
+    }
+
+    /// Exact number of the letter in the answer, if known
+    pub fn count(&self) -> Option<usize> {
+        (self.max == Some(self.min)).then_some(self.min)
+    }
+
+    /// Whether the letter is known to be at `pos`
+    pub fn is_correct_at(&self, pos: usize) -> bool {
+        pos < u32::BITS as usize && self.correct & (1 << pos) != 0
+    }
+
+    /// Whether the letter is known not to be at `pos`, or not to be in the answer at all
+    pub fn is_excluded_at(&self, pos: usize) -> bool {
+        self.max == Some(0) || (pos < u32::BITS as usize && self.excluded & (1 << pos) != 0)
+    }
+
+    /// Positions known to hold the letter, in order
+    pub fn correct_positions(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..u32::BITS as usize).filter(|pos| self.is_correct_at(*pos))
+    }
+
+    /// Best score the letter got, as shown on a keyboard
+    pub fn score(&self) -> LetterScore {
+        if self.correct != 0 {
+            LetterScore::Correct
+        }
+        else if self.min > 0 {
+            LetterScore::Present
+        }
+        else if self.max == Some(0) {
+            LetterScore::Wrong
+        }
+        else {
+            LetterScore::Unknown
+        }
+    }
+}
+
+
+/// Constraints on the answer from the feedback of every previous guess.
+/// A word matches the constraints exactly when it would have given the same feedback to every guess.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Knowledge {
+    length: usize,
+    letters: BTreeMap<char, LetterKnowledge>
+}
+
+impl Knowledge {
+    /// Nothing known yet about an answer of `length` letters
+    pub fn new(length: usize) -> Self {
+        Self {
+            length,
+            letters: BTreeMap::new()
+        }
+    }
+
+    /// Number of letters of the answer
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Adds the constraints of `guess` having got `pattern`
+    pub fn add(&mut self, guess: &str, pattern: Pattern) {
+        let scored: Vec<(usize, char, LetterScore)> = guess
+            .chars()
+            .zip(pattern.iter())
+            .enumerate()
+            .map(|(pos, (letter, score))| (pos, letter, score))
+            .collect();
+
+        for (pos, letter, score) in &scored {
+            let knowledge = self.letters.entry(*letter).or_default();
+            if *score == LetterScore::Correct {
+                knowledge.correct |= 1 << pos;
+            }
+            else {
+                knowledge.excluded |= 1 << pos;
+            }
+        }
+
+        for (_, letter, _) in &scored {
+            let found = scored
+                .iter()
+                .filter(|(_, l, s)| l == letter && *s >= LetterScore::Present)
+                .count();
+            // A wrong copy of the letter means the answer has no more of it
+            let complete = scored
+                .iter()
+                .any(|(_, l, s)| l == letter && *s == LetterScore::Wrong);
+
+            let knowledge = self.letters.get_mut(letter).unwrap();
+            knowledge.min = knowledge.min.max(found);
+            if complete {
+                knowledge.max = Some(knowledge.max.map_or(found, |max| max.min(found)));
+            }
+        }
+    }
+
+    /// Adds that `letter` is at `pos`, like a hint reveals.
+    /// Like the feedback of guesses, positions past [`Pattern::MAX_LEN`] are ignored
+    pub fn reveal(&mut self, letter: char, pos: usize) {
+        if pos >= Pattern::MAX_LEN {
+            return;
+        }
+        let knowledge = self.letters.entry(letter).or_default();
+        knowledge.correct |= 1 << pos;
+        knowledge.min = knowledge.min.max(knowledge.correct.count_ones() as usize);
+    }
+
+    /// What is known about `letter`
+    pub fn letter(&self, letter: char) -> LetterKnowledge {
+        self.letters.get(&letter).copied().unwrap_or_default()
+    }
+
+    /// Every letter of the previous guesses with what is known about it, in alphabetical order
+    pub fn letters(&self) -> impl Iterator<Item = (char, &LetterKnowledge)> {
+        self.letters.iter().map(|(letter, knowledge)| (*letter, knowledge))
+    }
+
+    /// Whether `letter` can be at `pos`, it cannot be where another letter is known to be
+    pub fn is_allowed(&self, letter: char, pos: usize) -> bool {
+        !self.letter(letter).is_excluded_at(pos)
+            && self.letters
+                .iter()
+                .all(|(other, knowledge)| *other == letter || !knowledge.is_correct_at(pos))
+    }
+
+    /// Whether `word` would have given the same feedback to every previous guess
+    pub fn matches(&self, word: &str) -> bool {
+        let word: Vec<char> = word.chars().collect();
+        word.len() == self.length
+            && self.letters.iter().all(|(letter, knowledge)| {
+                let count = word.iter().filter(|c| *c == letter).count();
+                count >= knowledge.min
+                    && knowledge.max.is_none_or(|max| count <= max)
+                    && word.iter().enumerate().all(|(pos, c)|
+                        if c == letter { !knowledge.is_excluded_at(pos) } else { !knowledge.is_correct_at(pos) }
+                    )
+            })
+    }
+
+    /// First revealed hint that `guess` does not use: a correct letter moved away,
+    /// then a present letter left out, as hard mode requires
+    pub fn missing_hint(&self, guess: &str) -> Option<InvalidWord> {
+        let guess: Vec<char> = guess.chars().collect();
+        let mut correct: Vec<(usize, char)> = self.letters
+            .iter()
+            .flat_map(|(letter, knowledge)| knowledge.correct_positions().map(move |pos| (pos, *letter)))
+            .collect();
+        correct.sort();
+
+        for (pos, letter) in correct {
+            if guess.get(pos) != Some(&letter) {
+                return Some(InvalidWord::MissingHint { letter, pos: Some(pos) });
+            }
+        }
+        for (letter, knowledge) in &self.letters {
+            if guess.iter().filter(|c| *c == letter).count() < knowledge.min {
+                return Some(InvalidWord::MissingHint { letter: *letter, pos: None });
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,37 @@
+//! Engine of the word game: word lists, dictionaries, games and their scoring.
+//!
+//! ```
+//! use word_game::{
+//!     dictionary::Dictionary,
+//!     wordle::{LetterScore, WordleGame}
+//! };
+//!
+//! let words: Dictionary = vec!["those".to_string(), "geese".to_string()].into();
+//! let mut game = WordleGame::new_with_answer(words, "those").unwrap();
+//!
+//! let scores = game.guess("geese").unwrap();
+//! assert_eq!(scores.get(4), Some(LetterScore::Correct));
+//! assert_eq!(scores.to_string(), "...gg");
+//! assert_eq!(game.lives(), 4);
+//! ```
+//!
+//! The terminal front-end lives in the `word_game` binary, built with the `tui` feature.
+//! The `serde` feature serializes games, scores, errors and word lists, see [`schema`].
+
+#![warn(missing_docs)]
+
+pub mod dictionary;
+pub mod history;
+pub mod knowledge;
+pub mod lint;
+pub mod pattern;
+#[cfg(feature = "serde")]
+pub mod protocol;
+#[cfg(feature = "serde")]
+pub mod schema;
+pub mod solver;
+pub mod stats;
+#[cfg(test)]
+mod tests;
+pub mod wordle;
+pub mod words;
@@ -0,0 +1,171 @@
+//! Issues of word lists and their fixes.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display
+};
+
+use unicode_normalization::is_nfc;
+
+use crate::words::{
+    self,
+    Entry,
+    WordError,
+    WordListHeader
+};
+
+
+
+type Findings = Vec<Finding>;
+
+/// What is wrong with a word of a list
+#[derive(Debug)]
+pub enum Issue {
+    /// The word cannot be played
+    Invalid {
+        /// Every reason it cannot be played
+        errors: Vec<WordError>
+    },
+    /// The word is already in the list
+    Duplicate {
+        /// Index of its first occurrence
+        first: usize
+    },
+    /// The word has uppercase letters
+    MixedCase,
+    /// The word is not in Unicode normalization form C
+    NotNormalized,
+    /// The answer cannot be guessed since the guess list does not have it
+    MissingFromGuesses
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid { errors } => {
+                let errors = errors
+                    .iter()
+                    .map(WordError::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "{errors}")
+            },
+            Self::Duplicate { first } => write!(f, "Duplicate of the word at index {first}"),
+            Self::MixedCase => write!(f, "Word is not lowercase"),
+            Self::NotNormalized => write!(f, "Word is not in Unicode normalization form C"),
+            Self::MissingFromGuesses => write!(f, "Answer is missing from the guess list")
+        }
+    }
+}
+
+
+/// An issue of a word of a list
+#[derive(Debug)]
+pub struct Finding {
+    /// Index of the word in the list
+    pub pos: usize,
+    /// Word as written in the list
+    pub word: String,
+    /// What is wrong with it
+    pub issue: Issue
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "- '{}' at index {}: {}", self.word, self.pos, self.issue)
+    }
+}
+
+
+
+/// Issues of every word of a list, in the order of the list
+pub fn lint(entries: &[Entry]) -> Findings {
+    let mut findings = Findings::new();
+
+    let words: Vec<&str> = entries.iter().map(|e| e.word.as_str()).collect();
+    if let Err((_, invalid)) = words::validate_list(&words) {
+        for word in invalid {
+            findings.push(Finding {
+                pos: entries[word.pos].pos,
+                word: word.word,
+                issue: Issue::Invalid { errors: word.errors }
+            });
+        }
+    }
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for entry in entries {
+        let mut issues = Vec::new();
+        if !is_nfc(&entry.word) {
+            issues.push(Issue::NotNormalized);
+        }
+        if entry.word != entry.word.to_lowercase() {
+            issues.push(Issue::MixedCase);
+        }
+        match seen.get(&words::canonicalize(&entry.word)) {
+            Some(&first) =>
+                issues.push(Issue::Duplicate { first }),
+            None => {
+                seen.insert(words::canonicalize(&entry.word), entry.pos);
+            }
+        }
+
+        findings.extend(issues.into_iter().map(|issue| Finding {
+            pos: entry.pos,
+            word: entry.word.clone(),
+            issue
+        }));
+    }
+
+    findings.sort_by_key(|f| f.pos);
+    findings
+}
+
+/// Answers that are not in the guess list, so they could never be guessed
+pub fn missing_answers(guesses: &[Entry], answers: &[Entry]) -> Findings {
+    let guesses: HashSet<String> = guesses.iter().map(|e| words::canonicalize(&e.word)).collect();
+    answers
+        .iter()
+        .filter(|e| !guesses.contains(&words::canonicalize(&e.word)))
+        .map(|e| Finding {
+            pos: e.pos,
+            word: e.word.clone(),
+            issue: Issue::MissingFromGuesses
+        })
+        .collect()
+}
+
+/// Valid words of the list, normalized, sorted and deduplicated, written in the text format
+pub fn fix(header: &WordListHeader, entries: &[Entry]) -> String {
+    let mut words: Vec<(String, Option<u64>)> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    for entry in entries {
+        if let Ok(word) = words::validate_word(&words::canonicalize(&entry.word)) {
+            if seen.insert(word.clone()) {
+                let frequency = entry.frequency.as_ref().and_then(|f| f.trim().parse().ok());
+                words.push((word, frequency));
+            }
+        }
+    }
+    words.sort();
+
+    let mut content = String::new();
+    if let Some(name) = &header.name {
+        content += &format!("#! name: {name}\n");
+    }
+    if let Some(language) = &header.language {
+        content += &format!("#! language: {language}\n");
+    }
+    if let Some(length) = &header.length {
+        content += &format!("#! length: {length}\n");
+    }
+    for (word, frequency) in words {
+        match frequency {
+            Some(frequency) =>
+                content += &format!("{word}\t{frequency}\n"),
+            None =>
+                content += &format!("{word}\n")
+        }
+    }
+    content
+}
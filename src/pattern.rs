@@ -0,0 +1,205 @@
+//! Scoring of guesses into compact feedback patterns.
+
+use std::{
+    fmt::Display,
+    str::FromStr
+};
+
+use crate::wordle::LetterScore;
+
+
+
+/// Feedback of a guess, one base-3 digit per letter starting from the least significant:
+/// `0` for a wrong letter, `1` for a present letter and `2` for a correct letter.
+/// Patterns of the same length are equal exactly when their feedback is,
+/// so they are cheap to compare, hash and group by.
+#[derive(
+    Debug, Default,
+    Clone, Copy,
+    PartialEq, Eq,
+    PartialOrd, Ord,
+    Hash
+)]
+/// Serialized as written by [`Display`], like `g.y..`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub struct Pattern {
+    code: u32,
+    len: u8
+}
+
+impl Pattern {
+    /// Longest feedback that fits in a pattern, `3^20` is the largest power of 3 in a `u32`
+    pub const MAX_LEN: usize = 20;
+
+    /// Pattern of `scores`, `None` if one is unknown or there are more than [`Self::MAX_LEN`]
+    pub fn new(scores: &[LetterScore]) -> Option<Self> {
+        if scores.len() > Self::MAX_LEN {
+            return None;
+        }
+        let mut pattern = Self::default();
+        for score in scores.iter().rev() {
+            let digit = match score {
+                LetterScore::Unknown => return None,
+                LetterScore::Wrong => 0,
+                LetterScore::Present => 1,
+                LetterScore::Correct => 2
+            };
+            pattern.code = pattern.code * 3 + digit;
+            pattern.len += 1;
+        }
+        Some(pattern)
+    }
+
+    /// Pattern of a guess equal to the answer
+    pub fn correct(len: usize) -> Self {
+        let len = len.min(Self::MAX_LEN);
+        Self {
+            code: 3u32.pow(len as u32) - 1,
+            len: len as u8
+        }
+    }
+
+    /// Base-3 encoding of the feedback, lower than `3^len`
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// Number of scored letters
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Whether no letter is scored
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Score of the letter at `pos`
+    pub fn get(&self, pos: usize) -> Option<LetterScore> {
+        if pos >= self.len() {
+            return None;
+        }
+        Some(match self.code / 3u32.pow(pos as u32) % 3 {
+            0 => LetterScore::Wrong,
+            1 => LetterScore::Present,
+            _ => LetterScore::Correct
+        })
+    }
+
+    /// Scores of the letters in order
+    pub fn iter(&self) -> impl Iterator<Item = LetterScore> + '_ {
+        (0..self.len()).map(|pos| self.get(pos).unwrap())
+    }
+
+    /// Whether every letter is correct, the guess is the answer
+    pub fn is_correct(&self) -> bool {
+        *self == Self::correct(self.len())
+    }
+
+    /// Scores of the letters in order, as a vector
+    pub fn to_vec(&self) -> Vec<LetterScore> {
+        self.iter().collect()
+    }
+}
+
+/// Writes the pattern like `g.y..`, `g` for a correct letter, `y` for a present letter and `.` for a wrong letter
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for score in self.iter() {
+            let char = match score {
+                LetterScore::Correct => 'g',
+                LetterScore::Present => 'y',
+                _ => '.'
+            };
+            write!(f, "{char}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads a pattern written like `g.y..`, any character but `g` and `y` is a wrong letter
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let scores: Vec<LetterScore> = value
+            .chars()
+            .map(|c| match c.to_ascii_lowercase() {
+                'g' => LetterScore::Correct,
+                'y' => LetterScore::Present,
+                _ => LetterScore::Wrong
+            })
+            .collect();
+        Self::new(&scores)
+            .ok_or_else(|| format!("Feedback is longer than {} letters", Self::MAX_LEN))
+    }
+}
+
+impl From<Pattern> for String {
+    fn from(pattern: Pattern) -> Self {
+        pattern.to_string()
+    }
+}
+
+impl TryFrom<String> for Pattern {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+
+
+/// Scores each letter of `guess` against `answer`.
+/// Letters at the same position are correct. Every other letter is present
+/// as many times as the answer has it left once the correct letters are removed,
+/// earlier positions first, and wrong after that.
+/// Letters of `guess` past the end of `answer` are wrong.
+///
+/// # Panics
+/// If `guess` has more than [`Pattern::MAX_LEN`] letters, see [`checked_score`] for words of any length
+pub fn score(answer: &str, guess: &str) -> Pattern {
+    match checked_score(answer, guess) {
+        Some(pattern) => pattern,
+        None => panic!("Guess is longer than {} letters", Pattern::MAX_LEN)
+    }
+}
+
+/// Scores `guess` like [`score`], `None` if it has more than [`Pattern::MAX_LEN`] letters
+pub fn checked_score(answer: &str, guess: &str) -> Option<Pattern> {
+    let answer: Vec<char> = answer.chars().collect();
+    let guess: Vec<char> = guess.chars().collect();
+    if guess.len() > Pattern::MAX_LEN {
+        return None;
+    }
+
+    // Letters of the answer that are not matched by a correct letter
+    let mut left: Vec<char> = answer
+        .iter()
+        .enumerate()
+        .filter(|(pos, letter)| guess.get(*pos) != Some(letter))
+        .map(|(_, letter)| *letter)
+        .collect();
+
+    let mut digits = [0; Pattern::MAX_LEN];
+    for (pos, letter) in guess.iter().enumerate() {
+        if answer.get(pos) == Some(letter) {
+            digits[pos] = 2;
+        }
+    }
+    for (pos, letter) in guess.iter().enumerate() {
+        if digits[pos] == 0 {
+            if let Some(index) = left.iter().position(|c| c == letter) {
+                left.swap_remove(index);
+                digits[pos] = 1;
+            }
+        }
+    }
+
+    Some(Pattern {
+        code: digits[..guess.len()].iter().rev().fold(0, |code, digit| code * 3 + digit),
+        len: guess.len() as u8
+    })
+}
@@ -0,0 +1,319 @@
+//! Line-based JSON protocol for bots and other front-ends: one request per input line,
+//! one [`Response`] per output line.
+
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::dictionary::Dictionary;
+use crate::wordle::{AnswerPicker, GameConfig, GameError, InvalidWord, LetterScore, WordleGame};
+
+
+
+/// A command read from a line of the input, like `{"command": "guess", "word": "crane"}`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Request {
+    /// Starts a game, replacing the current one. Missing fields use the command line settings
+    NewGame {
+        /// Seed of the answer picking
+        seed: Option<u64>,
+        /// Number of guesses
+        attempts: Option<usize>,
+        /// Whether guesses must use every revealed hint
+        hard: Option<bool>
+    },
+    /// Scores a guess
+    Guess {
+        /// Guessed word, canonicalized before being looked up
+        word: String
+    },
+    /// Reveals a letter of the answer
+    Hint,
+    /// Takes back the last guess or hint
+    Undo,
+    /// Answers with every guess of the current game
+    State,
+    /// Answers with the state and ends the session
+    Quit
+}
+
+
+/// State of the current game
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// No game was started, or the last one could not be
+    #[default]
+    NoGame,
+    /// The game goes on
+    InProgress,
+    /// The answer was guessed
+    Won,
+    /// There are no guesses left
+    Lost
+}
+
+
+/// A previous guess of the game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Try {
+    /// Guessed word
+    pub word: String,
+    /// Score of each letter of the guess
+    pub scores: Vec<LetterScore>
+}
+
+
+/// Why a request failed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Error {
+    /// Stable name of the error for programs, `message` is for humans
+    pub kind: &'static str,
+    /// Explanation of the error for players
+    pub message: String,
+    /// Letter the error is about, like a hint left out in hard mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub letter: Option<char>,
+    /// 1-based position the error is about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<usize>
+}
+
+impl Error {
+    fn new(kind: &'static str, message: impl ToString) -> Self {
+        Self { kind, message: message.to_string(), letter: None, position: None }
+    }
+}
+
+impl From<InvalidWord> for Error {
+    fn from(error: InvalidWord) -> Self {
+        let message = error.to_string();
+        match error {
+            InvalidWord::DifferentLength => Self::new("different_length", message),
+            InvalidWord::NotAWord => Self::new("not_a_word", message),
+            InvalidWord::MissingHint { letter, pos } => Self {
+                letter: Some(letter),
+                // Positions are 1-based like in the message
+                position: pos.map(|pos| pos + 1),
+                ..Self::new("missing_hint", message)
+            }
+        }
+    }
+}
+
+impl From<GameError> for Error {
+    fn from(error: GameError) -> Self {
+        let message = error.to_string();
+        match error {
+            GameError::AnswerNotInList { .. } => Self::new("answer_not_in_list", message),
+            GameError::NoAnswer => Self::new("no_answer", message),
+            GameError::UnsupportedLength { .. } => Self::new("unsupported_length", message),
+            GameError::UnsupportedLetter { letter } => Self {
+                letter: Some(letter),
+                ..Self::new("unsupported_letter", message)
+            },
+            GameError::NoAttempts => Self::new("no_attempts", message),
+            GameError::GameOver => Self::new("game_over", "The game is over, start a new one"),
+            GameError::NoHintLeft => Self::new("no_hint_left", message),
+            GameError::InvalidWord(error) => error.into()
+        }
+    }
+}
+
+
+/// A line of the output, answering a request
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Response {
+    /// State of the game after the request
+    pub status: Status,
+    /// Number of letters of the answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<usize>,
+    /// Guesses left
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lives: Option<usize>,
+    /// Scores of the word just guessed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scores: Option<Vec<LetterScore>>,
+    /// Letter revealed by a hint, with its 1-based position
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<(char, usize)>,
+    /// Every guess of the game, for `state` and `undo`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tries: Option<Vec<Try>>,
+    /// Only given once the game is over
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    /// Why the request failed, the rest of the response is then the unchanged state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Error>
+}
+
+
+/// Games played over the JSON protocol, all with the same words
+pub struct Session {
+    words: Dictionary,
+    picker: AnswerPicker,
+    config: GameConfig,
+    game: Option<WordleGame>,
+    closed: bool
+}
+
+impl Session {
+    /// A session without a game yet, new games use `words`, `picker` and `config` unless a request overrides them
+    pub fn new(words: Dictionary, picker: AnswerPicker, config: GameConfig) -> Self {
+        Self {
+            words,
+            picker,
+            config,
+            game: None,
+            closed: false
+        }
+    }
+
+    /// Whether a `quit` command was handled
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Answers a line of input
+    pub fn handle(&mut self, line: &str) -> Response {
+        let request = match serde_json::from_str::<Request>(line) {
+            Ok(request) => request,
+            Err(error) => return self.error(Error::new("invalid_command", error))
+        };
+
+        match request {
+            Request::NewGame { seed, attempts, hard } => {
+                let picker = AnswerPicker {
+                    seed: seed.or(self.picker.seed),
+                    ..self.picker.clone()
+                };
+                let config = GameConfig {
+                    attempts: attempts.or(self.config.attempts),
+                    hard: hard.unwrap_or(self.config.hard)
+                };
+                match WordleGame::new(self.words.clone(), &picker).and_then(|game| game.with_config(config)) {
+                    Ok(game) => {
+                        self.game = Some(game);
+                        self.state(false)
+                    },
+                    Err(error) => {
+                        self.game = None;
+                        self.error(error.into())
+                    }
+                }
+            },
+            Request::Guess { word } => {
+                let game = match &mut self.game {
+                    Some(game) => game,
+                    None => return self.error(Error::new("no_game", "There is no game, start a new one"))
+                };
+                match game.guess(&word) {
+                    Ok(scores) => Response {
+                        scores: Some(scores.to_vec()),
+                        ..self.state(false)
+                    },
+                    Err(error) => self.error(error.into())
+                }
+            },
+            Request::Hint => {
+                let game = match &mut self.game {
+                    Some(game) => game,
+                    None => return self.error(Error::new("no_game", "There is no game, start a new one"))
+                };
+                match game.hint() {
+                    Ok((letter, pos)) => Response {
+                        hint: Some((letter, pos + 1)),
+                        ..self.state(false)
+                    },
+                    Err(error) => self.error(error.into())
+                }
+            },
+            Request::Undo => {
+                if self.game.as_ref().is_some_and(WordleGame::is_over) {
+                    return self.error(GameError::GameOver.into());
+                }
+                match self.game.as_mut().map(WordleGame::undo) {
+                    Some(true) => self.state(true),
+                    Some(false) => self.error(Error::new("nothing_to_undo", "There is no guess or hint to take back")),
+                    None => self.error(Error::new("no_game", "There is no game, start a new one"))
+                }
+            },
+            Request::State =>
+                self.state(true),
+            Request::Quit => {
+                self.closed = true;
+                self.state(false)
+            }
+        }
+    }
+
+    fn state(&self, with_tries: bool) -> Response {
+        let game = match &self.game {
+            Some(game) => game,
+            None => return Response::default()
+        };
+        let status = status(game);
+
+        Response {
+            status,
+            length: Some(game.guess_empty().len()),
+            lives: Some(game.lives()),
+            scores: None,
+            hint: None,
+            tries: with_tries.then(|| game
+                .tries()
+                .iter()
+                .map(|(word, scores)| Try {
+                    word: word.clone(),
+                    scores: scores.to_vec()
+                })
+                .collect()
+            ),
+            answer: (status != Status::InProgress).then(|| game.answer().to_string()),
+            error: None
+        }
+    }
+
+    fn error(&self, error: Error) -> Response {
+        Response {
+            error: Some(error),
+            ..self.state(false)
+        }
+    }
+}
+
+
+/// Answers every line of `input` with a line of JSON on `output` until a `quit` command or the end of `input`
+pub fn run<R, W>(session: &mut Session, input: R, mut output: W) -> io::Result<()>
+    where R: BufRead, W: Write
+{
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        serde_json::to_writer(&mut output, &session.handle(&line))?;
+        writeln!(output)?;
+        output.flush()?;
+        if session.is_closed() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+
+fn status(game: &WordleGame) -> Status {
+    if game.is_won() {
+        Status::Won
+    }
+    else if game.is_over() {
+        Status::Lost
+    }
+    else {
+        Status::InProgress
+    }
+}
@@ -0,0 +1,111 @@
+//! Versioned serialization of the types of the engine, as JSON or as a compact binary format.
+//!
+//! Values are wrapped with the version of their schema, like `{"version": 1, "value": ...}` in JSON,
+//! so files written by older versions can still be read and newer ones are refused.
+//! Enums are written in snake case, [`Pattern`](crate::pattern::Pattern)s like `g.y..`
+//! and games as their [record](crate::history::Record), which is replayed with the words of the game when it is read.
+//!
+//! ```
+//! use word_game::{schema, wordle::{GameConfig, LetterScore}};
+//!
+//! let config = GameConfig { attempts: Some(4), hard: true };
+//! let json = schema::to_json(&config).unwrap();
+//! assert_eq!(json, r#"{"version":1,"value":{"attempts":4,"hard":true}}"#);
+//! assert_eq!(schema::from_json::<GameConfig>(&json), Ok(config));
+//!
+//! let bytes = schema::to_binary(&vec![LetterScore::Correct, LetterScore::Wrong]).unwrap();
+//! assert_eq!(schema::from_binary::<Vec<LetterScore>>(&bytes), Ok(vec![LetterScore::Correct, LetterScore::Wrong]));
+//! ```
+
+use std::fmt::Display;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+
+
+/// Version of the schemas written by this version of the engine
+pub const VERSION: u32 = 1;
+
+
+/// Why a value cannot be read or written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// Written by a newer version of the engine
+    UnsupportedVersion {
+        /// Version of the schema of the value
+        version: u32
+    },
+    /// Not a value of the expected type
+    Invalid {
+        /// Error of the JSON or binary format
+        reason: String
+    }
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedVersion { version } => write!(f, "Schema version {version} is not supported"),
+            Self::Invalid { reason } => write!(f, "Value cannot be read: {reason}")
+        }
+    }
+}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Invalid { reason: error.to_string() }
+    }
+}
+
+impl From<bincode::Error> for SchemaError {
+    fn from(error: bincode::Error) -> Self {
+        Self::Invalid { reason: error.to_string() }
+    }
+}
+
+
+#[derive(Serialize, Deserialize)]
+struct Versioned<T> {
+    version: u32,
+    value: T
+}
+
+/// Only the version, read first so that newer values are refused before their content
+#[derive(Deserialize)]
+struct Header {
+    version: u32
+}
+
+impl Header {
+    fn check(self) -> Result<(), SchemaError> {
+        if self.version > VERSION {
+            return Err(SchemaError::UnsupportedVersion { version: self.version });
+        }
+        Ok(())
+    }
+}
+
+
+
+/// Writes `value` as JSON with the version of its schema
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, SchemaError> {
+    Ok(serde_json::to_string(&Versioned { version: VERSION, value })?)
+}
+
+/// Reads a value written by [`to_json`], checking the version first
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, SchemaError> {
+    serde_json::from_str::<Header>(json)?.check()?;
+    Ok(serde_json::from_str::<Versioned<T>>(json)?.value)
+}
+
+/// Writes `value` with [bincode](https://docs.rs/bincode), smaller and faster to read than JSON
+pub fn to_binary<T: Serialize>(value: &T) -> Result<Vec<u8>, SchemaError> {
+    Ok(bincode::serialize(&Versioned { version: VERSION, value })?)
+}
+
+/// Reads a value written by [`to_binary`], checking the version first
+pub fn from_binary<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, SchemaError> {
+    // The version is the first field, the rest of the bytes is ignored
+    bincode::deserialize::<Header>(bytes)?.check()?;
+    Ok(bincode::deserialize::<Versioned<T>>(bytes)?.value)
+}
@@ -0,0 +1,76 @@
+//! Words that match the feedback of previous guesses, and the best next guess.
+
+use std::collections::HashSet;
+
+use crate::dictionary::Dictionary;
+use crate::knowledge::Knowledge;
+use crate::pattern::{self, Pattern};
+use crate::words;
+
+
+
+type Feedback = (String, Pattern);
+
+/// How many candidates are tried as the suggested next guess
+const SUGGESTION_CANDIDATES: usize = 500;
+
+
+/// Parses a guess with its feedback written as `guess=feedback`,
+/// where `g` is a correct letter, `y` is a present letter and anything else is a wrong letter
+pub fn parse_feedback(input: &str) -> Result<Feedback, String> {
+    let (guess, feedback) = match input.split_once('=') {
+        Some(split) =>
+            split,
+        None =>
+            return Err(format!("'{input}' should be written as 'guess=feedback'"))
+    };
+    let guess = words::canonicalize(guess);
+    if guess.chars().count() != feedback.chars().count() {
+        return Err(format!("'{input}' should have as much feedback as letters in the guess"));
+    }
+
+    Ok((guess, feedback.parse()?))
+}
+
+/// Words that would have given the same feedback to every guess if they were the answer
+pub fn candidates<'a>(words: &'a Dictionary, feedback: &[Feedback]) -> Vec<&'a str> {
+    let knowledge = match feedback.first() {
+        Some((guess, _)) => {
+            let mut knowledge = Knowledge::new(guess.chars().count());
+            for (guess, pattern) in feedback {
+                knowledge.add(guess, *pattern);
+            }
+            knowledge
+        },
+        None =>
+            return words.iter().collect()
+    };
+    // Guesses of different lengths cannot have the same answer
+    if feedback.iter().any(|(guess, _)| guess.chars().count() != knowledge.length()) {
+        return Vec::new();
+    }
+
+    words
+        .iter()
+        .filter(|word| knowledge.matches(word))
+        .collect()
+}
+
+/// Number of distinct feedbacks `guess` gets against `answers`, the more the better it splits them.
+/// A guess too long to be scored gets none
+pub fn distinct_patterns(guess: &str, answers: &[&str]) -> usize {
+    answers
+        .iter()
+        .filter_map(|answer| pattern::checked_score(answer, guess))
+        .collect::<HashSet<Pattern>>()
+        .len()
+}
+
+/// The candidate that splits the other candidates in the most groups
+pub fn suggest<'a>(candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .take(SUGGESTION_CANDIDATES)
+        .max_by_key(|guess| distinct_patterns(guess, candidates))
+        .copied()
+}
@@ -0,0 +1,159 @@
+//! Statistics about word lists: lengths, letter frequencies and good starting words.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde_json::{json, Value};
+
+use crate::pattern::Pattern;
+use crate::solver;
+
+
+
+/// How many starting words are suggested for every word length
+const STARTER_COUNT: usize = 5;
+/// How many of the best starting words by letter frequency get their feedback patterns counted
+const STARTER_CANDIDATES: usize = 20;
+
+
+/// A good first guess
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Starter {
+    /// Suggested word
+    pub word: String,
+    /// Number of distinct feedback patterns the word produces against every word of the same length
+    pub patterns: usize
+}
+
+/// Statistics about a list of words
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    /// Number of words
+    pub total: usize,
+    /// Number of words of each length
+    pub lengths: BTreeMap<usize, usize>,
+    /// Occurrences of each letter in every word
+    pub letters: BTreeMap<char, usize>,
+    /// Occurrences of each letter at each position
+    pub positions: Vec<BTreeMap<char, usize>>,
+    /// Best starting words for each word length that can be scored, best first
+    pub starters: BTreeMap<usize, Vec<Starter>>
+}
+
+impl Stats {
+    /// Counts the statistics of `words`
+    pub fn new<'a, I>(words: I) -> Self
+        where I: IntoIterator<Item = &'a str>
+    {
+        let mut stats = Self::default();
+
+        let mut by_length: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+        for word in words {
+            stats.total += 1;
+            let length = word.chars().count();
+            *stats.lengths.entry(length).or_default() += 1;
+            by_length.entry(length).or_default().push(word);
+
+            if stats.positions.len() < length {
+                stats.positions.resize(length, BTreeMap::new());
+            }
+            for (pos, char) in word.chars().enumerate() {
+                *stats.letters.entry(char).or_default() += 1;
+                *stats.positions[pos].entry(char).or_default() += 1;
+            }
+        }
+
+        // Longer words cannot be scored, so they cannot be compared as starting words
+        for (length, words) in by_length.range(..=Pattern::MAX_LEN) {
+            stats.starters.insert(*length, starters(words));
+        }
+
+        stats
+    }
+
+    /// The statistics as a JSON object with the names of the fields
+    pub fn to_json(&self) -> Value {
+        json!({
+            "total": self.total,
+            "lengths": self.lengths,
+            "letters": self.letters,
+            "positions": self.positions,
+            "starters": self.starters
+                .iter()
+                .map(|(length, starters)| (
+                    length.to_string(),
+                    starters
+                        .iter()
+                        .map(|s| json!({ "word": s.word, "patterns": s.patterns }))
+                        .collect::<Value>()
+                ))
+                .collect::<serde_json::Map<String, Value>>()
+        })
+    }
+
+    /// The statistics as text tables, one per statistic
+    pub fn to_table(&self) -> String {
+        let mut table = format!("Words: {}\n", self.total);
+
+        table += "\nLength  Count\n";
+        for (length, count) in &self.lengths {
+            table += &format!("{length:>6}  {count:>5}\n");
+        }
+
+        table += "\nLetter  Total";
+        for pos in 0..self.positions.len() {
+            table += &format!("  {:>5}", format!("#{}", pos + 1));
+        }
+        table += "\n";
+        for (char, count) in &self.letters {
+            table += &format!("{char:>6}  {count:>5}");
+            for position in &self.positions {
+                table += &format!("  {:>5}", position.get(char).unwrap_or(&0));
+            }
+            table += "\n";
+        }
+
+        table += "\nLength  Starting word  Patterns\n";
+        for (length, starters) in &self.starters {
+            for starter in starters {
+                table += &format!("{length:>6}  {:>13}  {:>8}\n", starter.word, starter.patterns);
+            }
+        }
+
+        table
+    }
+}
+
+
+
+/// Best starting words among words of the same length.
+/// Candidates with the most frequent distinct letters are ranked by how many feedback patterns they can produce.
+fn starters(words: &[&str]) -> Vec<Starter> {
+    let mut letters: HashMap<char, usize> = HashMap::new();
+    let mut positions: HashMap<(usize, char), usize> = HashMap::new();
+    for word in words {
+        for (pos, char) in word.chars().enumerate() {
+            *letters.entry(char).or_default() += 1;
+            *positions.entry((pos, char)).or_default() += 1;
+        }
+    }
+
+    let heuristic = |word: &str| -> usize {
+        let distinct: HashSet<char> = word.chars().collect();
+        distinct.iter().map(|c| letters[c]).sum::<usize>()
+            + word.chars().enumerate().map(|(pos, char)| positions[&(pos, char)]).sum::<usize>()
+    };
+    let mut candidates = words.to_vec();
+    candidates.sort_by_key(|word| std::cmp::Reverse(heuristic(word)));
+    candidates.truncate(STARTER_CANDIDATES);
+
+    let mut starters: Vec<Starter> = candidates
+        .into_iter()
+        .map(|guess| Starter {
+            word: guess.to_string(),
+            patterns: solver::distinct_patterns(guess, words)
+        })
+        .collect();
+    starters.sort_by(|a, b| b.patterns.cmp(&a.patterns).then_with(|| a.word.cmp(&b.word)));
+    starters.truncate(STARTER_COUNT);
+    starters
+}
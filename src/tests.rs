@@ -1,49 +1,752 @@
-#[cfg(test)]
-mod tests {
-    use crate::wordle::{
-        WordleGame,
-        LetterScore
-    };
+use std::{
+    collections::HashMap,
+    env,
+    fs,
+    io::Write
+};
 
-    #[test]
-    fn geese_test() {
-        let mut game = WordleGame::new_with_answer(
-            vec![
-                String::from("those"),
-                String::from("geese")
-            ],
-            "those"
+use flate2::{write::GzEncoder, Compression};
+use proptest::prelude::*;
+
+use crate::dictionary::{self, Dictionary};
+use crate::history::{self, Event, HistoryError};
+#[cfg(feature = "serde")]
+use crate::history::Record;
+use crate::knowledge::Knowledge;
+use crate::lint::{self, Issue};
+use crate::pattern::{self, Pattern};
+#[cfg(feature = "serde")]
+use crate::protocol::{self, Session, Status};
+#[cfg(feature = "serde")]
+use crate::schema::{self, SchemaError};
+use crate::stats::Stats;
+use crate::solver;
+use crate::wordle::{
+    AnswerPicker,
+    Difficulty,
+    GameConfig,
+    GameError,
+    InvalidWord,
+    WordleGame,
+    LetterScore
+};
+use crate::words::{
+    self,
+    WordListError,
+    WordListFormat
+};
+
+#[test]
+fn geese_test() {
+    let mut game = WordleGame::new_with_answer(
+        vec![
+            String::from("those"),
+            String::from("geese")
+        ].into(),
+        "those"
+    ).unwrap();
+
+    assert_eq!(&[
+        LetterScore::Wrong,
+        LetterScore::Wrong,
+        LetterScore::Wrong,
+        LetterScore::Correct,
+        LetterScore::Correct
+        ],
+        &game.guess(&String::from("geese")).unwrap().to_vec()[..],
+    );
+}
+
+#[test]
+fn added_test() {
+    let mut game = WordleGame::new_with_answer(
+        vec![
+            String::from("dread"),
+            String::from("added")
+        ].into(),
+        "dread"
+    ).unwrap();
+
+    assert_eq!(&[
+        LetterScore::Present,
+        LetterScore::Present,
+        LetterScore::Wrong,
+        LetterScore::Present,
+        LetterScore::Correct
+        ],
+        &game.guess(&String::from("added")).unwrap().to_vec()[..],
+    );
+}
+
+#[test]
+fn text_list_test() {
+    let list = words::parse(
+        "#! name: Test\n#! language: en\n#! length: 5\n# Comment\n\nThose\t120\ngeese\n",
+        WordListFormat::Text
+    ).unwrap();
+
+    assert_eq!(list.header.name.as_deref(), Some("Test"));
+    assert_eq!(list.header.language.as_deref(), Some("en"));
+    assert_eq!(list.header.length, Some(5));
+    assert_eq!(list.words.iter().collect::<Vec<&str>>(), ["geese", "those"]);
+    assert_eq!(list.frequencies.get("those"), Some(&120));
+    assert_eq!(list.frequencies.get("geese"), None);
+}
+
+#[test]
+fn text_list_errors_test() {
+    let (list, error) = words::parse(
+        "#! length: 5\nthose\ndread\tmany\nadd\n",
+        WordListFormat::Text
+    ).unwrap_err();
+
+    assert_eq!(list.words.iter().collect::<Vec<&str>>(), ["those"]);
+    match error {
+        WordListError::InvalidWords { words } => {
+            assert_eq!(words.len(), 2);
+            assert_eq!(words[0].pos, 2);
+            assert_eq!(words[1].pos, 3);
+        },
+        _ => panic!("Expected invalid words, got {error}")
+    }
+}
+
+#[test]
+fn json_list_test() {
+    let content = r#"["those", {"word": "geese", "frequency": 7}]"#;
+    assert_eq!(WordListFormat::detect("list", content), WordListFormat::Json);
+
+    let list = words::parse(content, WordListFormat::Json).unwrap();
+    assert_eq!(list.words.iter().collect::<Vec<&str>>(), ["geese", "those"]);
+    assert_eq!(list.frequencies.get("geese"), Some(&7));
+
+    assert!(matches!(
+        words::parse("{}", WordListFormat::Json),
+        Err((_, WordListError::InvalidFormat { .. }))
+    ));
+}
+
+#[test]
+fn gzip_list_test() {
+    let path = env::temp_dir().join(format!("word_game_{}.json.gz", std::process::id()));
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(br#"["those", "geese"]"#).unwrap();
+    fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+    let list = words::read_from(path.to_str().unwrap());
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(list.unwrap().words.iter().collect::<Vec<&str>>(), ["geese", "those"]);
+}
+
+#[test]
+fn difficulty_test() {
+    let words = vec![
+        String::from("those"),
+        String::from("geese"),
+        String::from("dread")
+    ];
+    let frequencies = HashMap::from([
+        (String::from("those"), 500),
+        (String::from("dread"), 0)
+    ]);
+
+    for difficulty in [Difficulty::Easy, Difficulty::Top(1)] {
+        let picker = AnswerPicker {
+            frequencies: frequencies.clone(),
+            difficulty,
+            ..Default::default()
+        };
+        let mut game = WordleGame::new(words.clone().into(), &picker).unwrap();
+        assert_eq!(
+            game.guess("those").unwrap(),
+            Pattern::correct(5)
         );
+    }
+}
+
+#[test]
+fn lint_test() {
+    let (header, entries) = words::parse_entries(
+        "#! name: Test\nThose\t5\ngeese\nthose\ncafe\u{301}\nab1de\n",
+        WordListFormat::Text
+    ).unwrap();
+
+    let findings = lint::lint(&entries);
+    let issues: Vec<(usize, &Issue)> = findings
+        .iter()
+        .map(|f| (f.pos, &f.issue))
+        .collect();
+    assert!(matches!(issues[0], (1, Issue::MixedCase)));
+    assert!(matches!(issues[1], (3, Issue::Duplicate { first: 1 })));
+    assert!(issues.iter().any(|i| matches!(i, (4, Issue::NotNormalized))));
+    assert!(issues.iter().any(|i| matches!(i, (5, Issue::Invalid { .. }))));
+
+    assert_eq!(
+        lint::fix(&header, &entries),
+        "#! name: Test\ncaf\u{e9}\ngeese\nthose\t5\n"
+    );
+}
 
-        assert_eq!(&[
-            LetterScore::Wrong,
-            LetterScore::Wrong,
-            LetterScore::Wrong,
-            LetterScore::Correct,
-            LetterScore::Correct
-            ],
-            &game.guess(&String::from("geese")).unwrap()[..],
+#[test]
+fn stats_test() {
+    let stats = Stats::new(["those", "geese", "add"]);
+
+    assert_eq!(stats.total, 3);
+    assert_eq!(stats.lengths.get(&5), Some(&2));
+    assert_eq!(stats.letters.get(&'e'), Some(&4));
+    assert_eq!(stats.positions[0].get(&'g'), Some(&1));
+    assert_eq!(stats.positions.len(), 5);
+    assert_eq!(stats.starters[&5][0].patterns, 2);
+    assert_eq!(stats.to_json()["lengths"]["3"], 1);
+
+    // Words too long to be scored are counted but not suggested
+    let long = "a".repeat(Pattern::MAX_LEN + 1);
+    let stats = Stats::new([long.as_str(), "those"]);
+    assert_eq!((stats.total, stats.starters.len()), (2, 1));
+    assert_eq!(solver::distinct_patterns(&long, &[long.as_str()]), 0);
+}
+
+#[test]
+fn normalization_test() {
+    let list = words::parse(
+        "Those \r\ncafe\u{301}\nthose\t3\ngeese\t2\ngeese\t9\ndread\r",
+        WordListFormat::Text
+    ).unwrap();
+
+    assert_eq!(list.words.iter().collect::<Vec<&str>>(), ["caf\u{e9}", "dread", "geese", "those"]);
+    assert!(list.words.contains("those"));
+    assert!(!list.words.contains("Those"));
+    assert_eq!(list.frequencies.get("geese"), Some(&2));
+    assert_eq!(list.normalization.changed, vec![
+        (String::from("Those "), String::from("those")),
+        (String::from("cafe\u{301}"), String::from("caf\u{e9}")),
+        (String::from("dread\r"), String::from("dread"))
+    ]);
+    assert_eq!(list.normalization.duplicates, vec!["those", "geese"]);
+
+    // Lengths count letters, not bytes
+    assert_eq!(words::validate_word("ñandú"), Ok(String::from("ñandú")));
+    assert_eq!(words::validate_word("собака"), Ok(String::from("собака")));
+    assert_eq!(words::validate_word("ñandúes"), Err(vec![words::WordError::InvalidLength { len: 7 }]));
+}
+
+#[test]
+fn dictionary_test() {
+    let dictionary = Dictionary::from(vec![
+        String::from("those"),
+        String::from("caf\u{e9}"),
+        String::from("abcdefghijklm")
+    ]);
+
+    assert!(dictionary.contains("those"));
+    assert!(dictionary.contains("caf\u{e9}"));
+    assert!(dictionary.contains("abcdefghijklm"));
+    assert!(!dictionary.contains("geese"));
+    assert!(!dictionary.contains("cafe"));
+
+    assert_eq!(dictionary::pack("a"), Some(1));
+    assert_eq!(dictionary::pack("ba"), Some(0b00010_00001));
+    assert_eq!(dictionary::pack("abcdefghijklm"), None);
+    assert_eq!(dictionary::pack("Those"), None);
+}
+
+#[test]
+fn bundled_dictionary_test() {
+    let bundled = Dictionary::bundled();
+
+    assert_eq!(bundled.len(), words::default_words().iter().count());
+    assert_eq!(bundled.iter().next(), Some("aahs"));
+    assert!(bundled.contains("those"));
+    assert!(bundled.contains("geese"));
+    assert!(!bundled.contains("qqqqq"));
+    assert!(!bundled.contains("tho"));
+    assert!(bundled.iter().all(|word| bundled.contains(word)));
+}
+
+#[test]
+fn blocklist_test() {
+    let words = Dictionary::from(vec![
+        String::from("those"),
+        String::from("geese")
+    ]);
+    let blocklist = Dictionary::from(vec![String::from("geese")]);
+
+    let picker = AnswerPicker {
+        blocklist: blocklist.clone(),
+        ..Default::default()
+    };
+    for _ in 0..20 {
+        let mut game = WordleGame::new(words.clone(), &picker).unwrap();
+        assert!(game.guess("geese").is_ok());
+        assert_eq!(
+            game.guess("those").unwrap(),
+            Pattern::correct(5)
         );
     }
 
+    let words = words.without(&blocklist);
+    assert!(!words.contains("geese"));
+    assert!(words.contains("those"));
+    assert!(words::default_blocklist().contains("shit"));
+}
+
+#[test]
+fn merged_lists_test() {
+    let dir = env::temp_dir().join(format!("word_game_merge_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+    fs::write(path("base.txt"), "#! name: Base\nthose\t1\ngeese\t2\nadded\n").unwrap();
+    fs::write(path("jargon.txt"), "#! name: Jargon\nthose\t10\nrusty\nab1de\n").unwrap();
+    fs::write(path("exclude.txt"), "added\n").unwrap();
+
+    let (list, errors) = words::read_all(
+        &[path("base.txt"), path("jargon.txt"), path("missing.txt")],
+        &[path("exclude.txt")]
+    );
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(list.words.iter().collect::<Vec<&str>>(), ["geese", "rusty", "those"]);
+    assert_eq!(list.frequencies.get("those"), Some(&10));
+    assert_eq!(list.frequencies.get("geese"), Some(&2));
+    assert_eq!(list.header.name.as_deref(), Some("Jargon"));
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].0, path("jargon.txt"));
+    assert!(matches!(errors[0].1, WordListError::InvalidWords { .. }));
+    assert_eq!(errors[1].0, path("missing.txt"));
+    assert!(matches!(errors[1].1, WordListError::NoFile));
+}
+
+#[test]
+fn seed_test() {
+    let picker = AnswerPicker {
+        seed: Some(42),
+        ..Default::default()
+    };
+    let answer = |words: Dictionary| {
+        let mut game = WordleGame::new(words.clone(), &picker).unwrap();
+        words.iter().find(|w| game.guess(w).unwrap().is_correct()).map(str::to_string)
+    };
+    let words: Dictionary = ["those", "geese", "dread", "added"].map(String::from).to_vec().into();
+
+    assert_eq!(answer(words.clone()), answer(words.clone()));
+}
+
+#[test]
+fn hard_mode_test() {
+    let words: Dictionary = ["those", "geese", "thorn", "shoes", "tiles"].map(String::from).to_vec().into();
+    let mut game = WordleGame::new_with_answer(words, "those")
+        .unwrap()
+        .with_config(GameConfig { attempts: Some(3), hard: true })
+        .unwrap();
+
+    assert_eq!(game.lives(), 3);
+    game.guess("tiles").unwrap();
+    assert!(matches!(game.guess("geese"), Err(GameError::InvalidWord(InvalidWord::MissingHint { letter: 't', pos: Some(0) }))));
+    assert!(matches!(game.guess("thorn"), Err(GameError::InvalidWord(InvalidWord::MissingHint { letter: 'e', pos: None }))));
+    assert!(game.guess("those").is_ok());
+}
+
+#[test]
+fn solver_test() {
+    let words: Dictionary = ["those", "geese", "thorn", "shoes", "tiles"].map(String::from).to_vec().into();
+    let feedback = vec![solver::parse_feedback("tiles=g..yy").unwrap()];
+
+    assert_eq!(solver::candidates(&words, &feedback), ["those"]);
+    assert!(solver::parse_feedback("tiles=g").is_err());
+    assert!(solver::parse_feedback("tiles").is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn protocol_test() {
+    let words: Dictionary = ["those", "geese", "thorn", "shoes", "tiles"].map(String::from).to_vec().into();
+    let picker = AnswerPicker { answers: Some(vec!["those".to_string()].into()), ..Default::default() };
+    let mut session = Session::new(words.clone(), picker.clone(), GameConfig { attempts: Some(2), hard: true });
+
+    assert_eq!(session.handle(r#"{"command": "guess", "word": "tiles"}"#).error.unwrap().kind, "no_game");
+    assert_eq!(session.handle(r#"{"command": "new_game"}"#).lives, Some(2));
+    let response = session.handle(r#"{"command": "guess", "word": "TILES"}"#);
+    assert_eq!(
+        serde_json::to_value(&response).unwrap()["scores"],
+        serde_json::json!(["correct", "wrong", "wrong", "present", "present"])
+    );
+    let error = session.handle(r#"{"command": "guess", "word": "geese"}"#).error.unwrap();
+    assert_eq!((error.kind, error.letter, error.position), ("missing_hint", Some('t'), Some(1)));
+    assert_eq!(session.handle(r#"{"command": "jump"}"#).error.unwrap().kind, "invalid_command");
+    let response = session.handle(r#"{"command": "guess", "word": "those"}"#);
+    assert_eq!((response.status, response.answer.as_deref()), (Status::Won, Some("those")));
+
+    let mut output = Vec::new();
+    protocol::run(&mut session, "{\"command\": \"state\"}\n{\"command\": \"quit\"}\n{\"command\": \"state\"}\n".as_bytes(), &mut output).unwrap();
+    let lines: Vec<serde_json::Value> = String::from_utf8(output).unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["tries"][1]["word"], "those");
+    assert_eq!(lines[0]["status"], "won");
+
+    let mut session = Session::new(words.clone(), picker, GameConfig::default());
+    session.handle(r#"{"command": "new_game"}"#);
+    assert_eq!(session.handle(r#"{"command": "hint"}"#).hint, Some(('t', 1)));
+    assert_eq!(session.handle(r#"{"command": "undo"}"#).hint, None);
+    assert_eq!(session.handle(r#"{"command": "undo"}"#).error.unwrap().kind, "nothing_to_undo");
+
+    // A game without attempts would be lost before any guess
+    let response = session.handle(r#"{"command": "new_game", "attempts": 0}"#);
+    assert_eq!((response.status, response.error.unwrap().kind), (Status::NoGame, "no_attempts"));
+
+    // The answer of a lost game is revealed, so it cannot be taken back
+    session.handle(r#"{"command": "new_game", "attempts": 1}"#);
+    assert_eq!(session.handle(r#"{"command": "guess", "word": "geese"}"#).status, Status::Lost);
+    let response = session.handle(r#"{"command": "undo"}"#);
+    assert_eq!((response.status, response.error.unwrap().kind), (Status::Lost, "game_over"));
+
+    // Each reason a game cannot start has its own kind
+    for (answers, kind) in [(vec![], "no_answer"), (vec!["crane".to_string()], "answer_not_in_list")] {
+        let picker = AnswerPicker { answers: Some(answers.into()), ..Default::default() };
+        let mut session = Session::new(words.clone(), picker, GameConfig::default());
+        assert_eq!(session.handle(r#"{"command": "new_game"}"#).error.unwrap().kind, kind);
+    }
+    let long: Dictionary = vec!["a".repeat(30)].into();
+    let mut session = Session::new(long, AnswerPicker::default(), GameConfig::default());
+    assert_eq!(session.handle(r#"{"command": "new_game"}"#).error.unwrap().kind, "unsupported_length");
+}
+
+/// Scores straight from the rules: a letter is present if the answer has more of it,
+/// not counting correct letters, than the earlier present or wrong guesses of it
+fn reference_score(answer: &str, guess: &str) -> Vec<LetterScore> {
+    let answer: Vec<char> = answer.chars().collect();
+    let guess: Vec<char> = guess.chars().collect();
+    let correct = |pos: usize| answer[pos] == guess[pos];
+
+    (0..guess.len())
+        .map(|pos| {
+            let letter = guess[pos];
+            let in_answer = (0..answer.len()).filter(|&i| answer[i] == letter && !correct(i)).count();
+            let before = (0..pos).filter(|&i| guess[i] == letter && !correct(i)).count();
+            if correct(pos) {
+                LetterScore::Correct
+            }
+            else if in_answer > before {
+                LetterScore::Present
+            }
+            else {
+                LetterScore::Wrong
+            }
+        })
+        .collect()
+}
+
+/// Every word of `length` letters from `alphabet`
+fn all_words(alphabet: &str, length: usize) -> Vec<String> {
+    (0..length).fold(vec![String::new()], |words, _| {
+        words
+            .iter()
+            .flat_map(|word| alphabet.chars().map(move |c| format!("{word}{c}")))
+            .collect()
+    })
+}
+
+#[test]
+fn score_test() {
+    for length in 1..=5 {
+        let words = all_words("abc", length);
+        for answer in &words {
+            for guess in &words {
+                let pattern = pattern::score(answer, guess);
+                assert_eq!(pattern.to_vec(), reference_score(answer, guess), "{guess} against {answer}");
+                assert_eq!(pattern.is_correct(), answer == guess);
+            }
+        }
+    }
+
+    for (answer, guess, expected) in [
+        ("dread", "added", "yy.yg"),
+        ("abide", "speed", "..y.y"),
+        ("those", "geese", "...gg"),
+        ("lolly", "alloy", ".ygyg"),
+        ("crane", "eerie", "..y.g"),
+        ("tête", "êtes", "yyy.")
+    ] {
+        assert_eq!(pattern::score(answer, guess).to_string(), expected, "{guess} against {answer}");
+    }    assert_eq!(pattern::checked_score("those", &"a".repeat(Pattern::MAX_LEN + 1)), None);
+}
+
+/// Answers and guesses of the same length, from a few letters so that they share many
+fn word_pairs() -> impl Strategy<Value = (String, String)> {
+    let letter = prop::sample::select(vec!['a', 'b', 'e', 'é', 'ß', 'ñ']);
+    (1..=Pattern::MAX_LEN).prop_flat_map(move |length| (
+        prop::collection::vec(letter.clone(), length).prop_map(String::from_iter),
+        prop::collection::vec(letter.clone(), length).prop_map(String::from_iter)
+    ))
+}
+
+proptest! {
+    #[test]
+    fn score_properties((answer, guess) in word_pairs()) {
+        let pattern = pattern::score(&answer, &guess);
+        prop_assert_eq!(pattern.len(), guess.chars().count());
+        prop_assert_eq!(pattern.to_vec(), reference_score(&answer, &guess));
+        prop_assert_eq!(pattern.is_correct(), answer == guess);
+        prop_assert!(pattern::score(&answer, &answer).is_correct());
+
+        // A letter is never found more times than the answer has it
+        for letter in guess.chars() {
+            let found = guess
+                .chars()
+                .zip(pattern.iter())
+                .filter(|(c, score)| *c == letter && *score >= LetterScore::Present)
+                .count();
+            prop_assert!(found <= answer.chars().filter(|c| *c == letter).count());
+        }
+
+        // The feedback never rules out the answer
+        let mut knowledge = Knowledge::new(answer.chars().count());
+        knowledge.add(&guess, pattern);
+        prop_assert!(knowledge.matches(&answer));
+    }
+
     #[test]
-    fn added_test() {
-        let mut game = WordleGame::new_with_answer(
-            vec![
-                String::from("dread"),
-                String::from("added")
-            ],
-            "dread"
-        );
+    fn word_list_properties(content in any::<String>()) {
+        for format in [WordListFormat::Text, WordListFormat::Json] {
+            let _ = words::parse(&content, format);
+        }
 
-        assert_eq!(&[
-            LetterScore::Present,
-            LetterScore::Present,
-            LetterScore::Wrong,
-            LetterScore::Present,
-            LetterScore::Correct
-            ],
-            &game.guess(&String::from("added")).unwrap()[..],
-        );
+        // Validated words are valid as they are
+        let lines: Vec<&str> = content.lines().collect();
+        let valid = match words::validate_list(&lines) {
+            Ok(valid) | Err((valid, _)) => valid
+        };
+        for word in valid {
+            prop_assert_eq!(words::validate_word(&word).ok(), Some(word.clone()));
+        }
     }
 }
+
+#[test]
+fn pattern_test() {
+    for code in 0..3u32.pow(5) {
+        let scores: Vec<LetterScore> = (0..5)
+            .map(|pos| [LetterScore::Wrong, LetterScore::Present, LetterScore::Correct][(code / 3u32.pow(pos) % 3) as usize])
+            .collect();
+        let pattern = Pattern::new(&scores).unwrap();
+
+        assert_eq!(pattern.code(), code);
+        assert_eq!(pattern.to_vec(), scores);
+        assert_eq!(pattern.to_string().parse::<Pattern>(), Ok(pattern));
+    }
+
+    assert_eq!(Pattern::correct(5).to_string(), "ggggg");
+    assert_eq!(Pattern::correct(Pattern::MAX_LEN).len(), Pattern::MAX_LEN);
+    assert_eq!(Pattern::new(&[LetterScore::Unknown]), None);
+    assert_eq!(Pattern::new(&[LetterScore::Wrong; Pattern::MAX_LEN + 1]), None);
+    assert!(Pattern::default().is_empty());
+}
+
+#[test]
+fn knowledge_test() {
+    // Matching the constraints of a guess is the same as getting its pattern
+    let words = all_words("abc", 4);
+    for answer in &words {
+        for guess in ["abca", "ccab", "bbbb"] {
+            let mut knowledge = Knowledge::new(4);
+            knowledge.add(guess, pattern::score(answer, guess));
+            for word in &words {
+                assert_eq!(
+                    knowledge.matches(word),
+                    pattern::score(word, guess) == pattern::score(answer, guess),
+                    "{word} with {guess} against {answer}"
+                );
+            }
+        }
+    }
+
+    let mut knowledge = Knowledge::new(5);
+    knowledge.add("geese", pattern::score("shoes", "geese"));
+    knowledge.add("sassy", pattern::score("shoes", "sassy"));
+    knowledge.add("boost", pattern::score("shoes", "boost"));
+
+    let e = knowledge.letter('e');
+    assert_eq!((e.min(), e.max()), (1, Some(1)));
+    assert!(e.is_excluded_at(1) && !e.is_excluded_at(3));
+    assert_eq!(knowledge.letter('s').count(), Some(2));
+    let o = knowledge.letter('o');
+    assert_eq!(o.correct_positions().collect::<Vec<_>>(), [2]);
+    assert_eq!(o.count(), Some(1));
+    assert_eq!(knowledge.letter('g').score(), LetterScore::Wrong);
+    assert_eq!(knowledge.letter('z').score(), LetterScore::Unknown);
+    assert!(!knowledge.is_allowed('e', 2));
+    assert!(knowledge.matches("shoes"));
+    assert!(!knowledge.matches("shoos"));
+    assert!(matches!(knowledge.missing_hint("sheep"), Some(InvalidWord::MissingHint { letter: 'o', pos: Some(2) })));
+
+    // Hints past the positions a pattern can score are ignored instead of overflowing
+    let mut knowledge = Knowledge::new(40);
+    knowledge.reveal('a', 3);
+    knowledge.reveal('b', 32);
+    knowledge.reveal('c', Pattern::MAX_LEN);
+    assert_eq!(knowledge.letter('a').correct_positions().collect::<Vec<_>>(), [3]);
+    assert_eq!((knowledge.letter('b'), knowledge.letter('c')), (Default::default(), Default::default()));
+}
+
+#[test]
+fn game_error_test() {
+    let words: Dictionary = ["those", "geese"].map(String::from).to_vec().into();
+
+    assert_eq!(
+        WordleGame::new_with_answer(words.clone(), "demo").unwrap_err(),
+        GameError::AnswerNotInList { answer: "demo".to_string() }
+    );
+    assert_eq!(
+        WordleGame::new_with_answer(words.clone(), "th0se").unwrap_err(),
+        GameError::UnsupportedLetter { letter: '0' }
+    );
+    let picker = AnswerPicker { blocklist: words.clone(), ..Default::default() };
+    assert_eq!(WordleGame::new(words.clone(), &picker).unwrap_err(), GameError::NoAnswer);
+    assert_eq!(WordleGame::new(Dictionary::default(), &AnswerPicker::default()).unwrap_err(), GameError::NoAnswer);
+
+    let mut game = WordleGame::new_with_answer(words.clone(), "those")
+        .unwrap()
+        .with_config(GameConfig { attempts: Some(1), hard: false })
+        .unwrap();
+    assert_eq!(game.guess("geese!").unwrap_err(), GameError::InvalidWord(InvalidWord::DifferentLength));
+    game.guess("geese").unwrap();
+    assert!(game.is_over() && !game.is_won());
+    assert_eq!(game.guess("those").unwrap_err(), GameError::GameOver);
+    assert_eq!(game.lives(), 0);
+
+    // A game without attempts would be lost before any guess
+    let game = WordleGame::new_with_answer(words.clone(), "those").unwrap();
+    assert_eq!(game.with_config(GameConfig { attempts: Some(0), hard: false }).err(), Some(GameError::NoAttempts));
+
+    let mut game = WordleGame::new_with_answer(words, "those").unwrap();
+    game.guess("those").unwrap();
+    assert!(game.is_won());
+    assert_eq!(game.guess("geese").unwrap_err(), GameError::GameOver);
+}
+
+#[test]
+fn history_test() {
+    let words: Dictionary = ["those", "geese", "thorn", "shoes", "tiles"].map(String::from).to_vec().into();
+    let picker = AnswerPicker { answers: Some(vec!["those".to_string()].into()), seed: Some(42), ..Default::default() };
+    let mut game = WordleGame::new(words.clone(), &picker)
+        .and_then(|game| game.with_config(GameConfig { attempts: Some(4), hard: false }))
+        .unwrap();
+    assert_eq!(
+        game.history(),
+        [Event::Created { answer: "those".to_string(), attempts: 4, hard: false, seed: Some(42) }]
+    );
+
+    // Hints reveal the first position not known yet and do not cost a guess
+    game.guess("thorn").unwrap();
+    assert_eq!(game.hint(), Ok(('s', 3)));
+    assert!(game.knowledge().letter('s').is_correct_at(3));
+    assert_eq!(game.lives(), 3);
+
+    // Undoing forgets the hint, then the guess
+    let before_hint = game.history()[..2].to_vec();
+    assert!(game.undo());
+    assert_eq!(game.history(), before_hint);
+    assert!(game.undo());
+    assert_eq!((game.lives(), game.tries().len()), (4, 0));
+    assert!(!game.undo());
+
+    game.guess("tiles").unwrap();
+    game.hint().unwrap();
+    game.guess("those").unwrap();
+    assert_eq!(game.history().last(), Some(&Event::Ended { won: true }));
+    assert!(!game.undo());
+    assert!(game.is_won());
+
+    // Replay files give back the same game
+    let text = history::to_text(&game.record());
+    let header = format!("#! version: 1\n#! words: {}\n", words.fingerprint());
+    assert!(text.starts_with(&(header + "created those attempts=4 hard=false seed=42\nguessed tiles g..yy\n")));
+    let record = history::parse(&text).unwrap();
+    assert_eq!((record.words, record.events.as_slice()), (Some(words.fingerprint()), game.history()));
+    let replayed = WordleGame::from_record(words.clone(), &record).unwrap();
+    assert_eq!((replayed.tries(), replayed.lives(), replayed.is_won()), (game.tries(), game.lives(), true));
+    let events = record.events;
+
+    // Files written without the fingerprint of their words are still replayed
+    let unchecked = history::parse(&text.replace("#! words:", "# words:")).unwrap();
+    assert_eq!(unchecked.words, None);
+    assert!(WordleGame::from_record(words.clone(), &unchecked).is_ok());
+
+    // Tampered and unsupported files, and files of other words are refused
+    let tampered = history::parse(&text.replace("g..yy", "ggggg")).unwrap();
+    assert!(matches!(WordleGame::from_record(words.clone(), &tampered), Err(HistoryError::Mismatch { .. })));
+    let other_words = words.without(&vec!["geese".to_string()].into());
+    assert_eq!(
+        WordleGame::from_record(other_words.clone(), &history::parse(&text).unwrap()).err(),
+        Some(HistoryError::OtherWords { expected: words.fingerprint(), found: other_words.fingerprint() })
+    );
+    assert!(matches!(history::parse("#! words: 12\n"), Err(HistoryError::InvalidEvent { line: 1, .. })));
+    assert_eq!(
+        history::parse("#! version: 2\n"),
+        Err(HistoryError::UnsupportedVersion { version: "2".to_string() })
+    );
+    assert!(matches!(history::parse("guessed those gg"), Err(HistoryError::InvalidEvent { line: 1, .. })));
+    assert_eq!(WordleGame::from_history(words, &events[1..]).err(), Some(HistoryError::MisplacedCreation));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn schema_test() {
+    fn round_trip<T>(value: &T)
+        where T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug
+    {
+        assert_eq!(&schema::from_json::<T>(&schema::to_json(value).unwrap()).unwrap(), value);
+        assert_eq!(&schema::from_binary::<T>(&schema::to_binary(value).unwrap()).unwrap(), value);
+    }
+
+    round_trip(&vec![LetterScore::Unknown, LetterScore::Wrong, LetterScore::Present, LetterScore::Correct]);
+    round_trip(&"g.y..".parse::<Pattern>().unwrap());
+    round_trip(&GameConfig { attempts: Some(3), hard: true });
+    round_trip(&InvalidWord::MissingHint { letter: 'é', pos: Some(2) });
+    round_trip(&GameError::InvalidWord(InvalidWord::NotAWord));
+    round_trip(&WordListError::InvalidWords {
+        words: vec![words::InvalidWord {
+            pos: 3,
+            word: "ab1".to_string(),
+            errors: vec![words::WordError::InvalidCharacter { pos: 2, char: '1' }]
+        }]
+    });
+    round_trip(&Event::Guessed { word: "tiles".to_string(), pattern: "g..yy".parse().unwrap() });
+
+    // Games are stored as their record, and replayed with their words when read
+    let words: Dictionary = ["those", "tiles", "thorn"].map(String::from).to_vec().into();
+    let mut game = WordleGame::new_with_answer(words.clone(), "those").unwrap();
+    game.guess("tiles").unwrap();
+    game.hint().unwrap();
+    round_trip(&game.record());
+    for record in [
+        schema::from_json::<Record>(&schema::to_json(&game).unwrap()).unwrap(),
+        schema::from_binary::<Record>(&schema::to_binary(&game).unwrap()).unwrap()
+    ] {
+        let restored = WordleGame::from_record(words.clone(), &record).unwrap();
+        assert_eq!(restored.history(), game.history());
+        assert_eq!((restored.tries(), restored.lives()), (game.tries(), game.lives()));
+        assert_eq!(restored.knowledge(), game.knowledge());
+    }
+
+    // The schema is stable
+    let json = schema::to_json(&game).unwrap();
+    assert_eq!(
+        json,
+        r#"{"version":1,"value":{"words":"b1ec192dc1cd5288","events":[{"created":{"answer":"those","attempts":5,"hard":false,"seed":null}},{"guessed":{"word":"tiles","pattern":"g..yy"}},{"hint_used":{"letter":"h","pos":1}}]}}"#
+    );
+    let tampered = json.replace("g..yy", "ggggg");
+    let tampered = schema::from_json::<Record>(&tampered).unwrap();
+    assert!(matches!(WordleGame::from_record(words, &tampered), Err(HistoryError::Mismatch { .. })));
+    assert_eq!(
+        schema::from_json::<GameConfig>(r#"{"version":2,"value":{"future":true}}"#),
+        Err(SchemaError::UnsupportedVersion { version: 2 })
+    );
+    let mut bytes = schema::to_binary(&GameConfig::default()).unwrap();
+    bytes[0] = 2;
+    assert_eq!(schema::from_binary::<GameConfig>(&bytes), Err(SchemaError::UnsupportedVersion { version: 2 }));
+}
@@ -1,30 +1,119 @@
+//! Games, their answers and the scoring of guesses.
+
 use std::{
+    collections::HashMap,
     fmt::Display,
     iter
 };
 
-use rand::seq::SliceRandom;
+use rand::{
+    distributions::WeightedIndex,
+    prelude::Distribution,
+    rngs::StdRng,
+    seq::SliceRandom,
+    Rng,
+    SeedableRng
+};
+
+use crate::dictionary::Dictionary;
+use crate::history::{Event, HistoryError, Record};
+use crate::knowledge::Knowledge;
+use crate::pattern::{self, Pattern};
+use crate::words;
 
 
 
+/// What a guess tells about a letter, ordered from the least to the most informative
 #[derive(
     Debug,
     Clone, Copy,
     PartialEq, PartialOrd, Eq,
     Hash
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum LetterScore {
+    /// Not guessed yet
     Unknown,
+    /// Not in the answer, or not as many times
     Wrong,
+    /// In the answer at another position
     Present,
+    /// In the answer at this position
     Correct
 }
 
 
-#[derive(Debug)]
+/// How the frequencies of the words weigh on the picking of the answer
+#[derive(
+    Debug, Default,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Difficulty {
+    /// Answers are picked proportionally to their frequency
+    Easy,
+    /// Every word is equally likely to be the answer
+    #[default]
+    Normal,
+    /// Answers are picked inversely proportionally to their frequency
+    Hard,
+    /// Answers are picked uniformly among the given number of most frequent words
+    Top(usize)
+}
+
+
+/// Where and how the answer of a game is picked
+#[derive(Debug, Default, Clone)]
+pub struct AnswerPicker {
+    /// Words the answer is picked from, all the words of the game if `None`
+    pub answers: Option<Dictionary>,
+    /// Frequencies of the words from their lists, missing words have a frequency of 0
+    pub frequencies: HashMap<String, u64>,
+    /// Words that are never picked
+    pub blocklist: Dictionary,
+    /// How the frequencies weigh on the picking
+    pub difficulty: Difficulty,
+    /// Seed of the random generator, the same seed and words give the same answer
+    pub seed: Option<u64>
+}
+
+
+/// Rules of a game
+#[derive(
+    Debug, Default,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct GameConfig {
+    /// Number of guesses, the length of the answer if `None`
+    pub attempts: Option<usize>,
+    /// Guesses must use every revealed hint
+    pub hard: bool
+}
+
+
+/// Why a guess is refused, refused guesses do not cost a life
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum InvalidWord {
+    /// The guess does not have as many letters as the answer
     DifferentLength,
-    NotAWord
+    /// The guess is not in the dictionary of the game
+    NotAWord,
+    /// In hard mode, the guess ignores a revealed letter,
+    /// at position `pos` if it was correct
+    MissingHint {
+        /// Revealed letter
+        letter: char,
+        /// 0-based position of the letter if it was correct
+        pos: Option<usize>
+    }
 }
 
 impl Display for InvalidWord {
@@ -32,122 +121,368 @@ impl Display for InvalidWord {
         match self {
             Self::DifferentLength => write!(f, "Input should be the same length as the word"),
             Self::NotAWord => write!(f, "This word is not in a dictionary"),
+            Self::MissingHint { letter, pos: Some(pos) } =>
+                write!(f, "Letter {} should be at position {}", letter.to_uppercase(), pos + 1),
+            Self::MissingHint { letter, pos: None } =>
+                write!(f, "Letter {} should be in the word", letter.to_uppercase()),
         }
     }
 }
 
 
+/// Why a game cannot be started or played on
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum GameError {
+    /// The answer is not in the dictionary of the game, so it could never be guessed
+    AnswerNotInList {
+        /// Given answer
+        answer: String
+    },
+    /// There is no word to pick the answer from
+    NoAnswer,
+    /// The answer has a letter that is not alphabetic
+    UnsupportedLetter {
+        /// First letter that is not alphabetic
+        letter: char
+    },
+    /// The answer is longer than a [`Pattern`] can score
+    UnsupportedLength {
+        /// Number of letters of the answer
+        len: usize
+    },
+    /// A game cannot start without a guess
+    NoAttempts,
+    /// The game is won or there are no guesses left
+    GameOver,
+    /// Every letter of the answer is already known
+    NoHintLeft,
+    /// The guess is refused
+    InvalidWord(InvalidWord)
+}
 
-#[derive(Debug)]
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AnswerNotInList { answer } => write!(f, "Answer '{answer}' is not in the word list"),
+            Self::NoAnswer => write!(f, "There is no word to pick the answer from"),
+            Self::UnsupportedLetter { letter } => write!(f, "Letter '{letter}' cannot be in a word"),
+            Self::UnsupportedLength { len } =>
+                write!(f, "Answer has {len} letters, it cannot have more than {}", Pattern::MAX_LEN),
+            Self::NoAttempts => write!(f, "A game needs at least one attempt"),
+            Self::GameOver => write!(f, "The game is over"),
+            Self::NoHintLeft => write!(f, "Every letter is already known"),
+            Self::InvalidWord(error) => write!(f, "{error}")
+        }
+    }
+}
+
+impl From<InvalidWord> for GameError {
+    fn from(error: InvalidWord) -> Self {
+        Self::InvalidWord(error)
+    }
+}
+
+
+
+/// A game with its dictionary, answer and previous guesses.
+/// Everything that happens is recorded in its history, which rebuilds the game when replayed
+#[derive(Debug, Clone)]
 pub struct WordleGame {
-    words: Vec<String>,
+    words: Dictionary,
     answer: String,
     lives: usize,
-    guesses: [LetterScore; 26]
+    hard: bool,
+    tries: Vec<(String, Pattern)>,
+    knowledge: Knowledge,
+    history: Vec<Event>
 }
 
 impl WordleGame {
-    pub fn new_with_answer(words: Vec<String>, answer: &str) -> Self {
-        if !words.contains(&answer.to_string()) {
-            panic!("Word {answer} is not in the given word list");
+    /// Starts a game with the given answer, which must be in `words`
+    pub fn new_with_answer(words: Dictionary, answer: &str) -> Result<Self, GameError> {
+        let length = answer.chars().count();
+        if let Some(letter) = answer.chars().find(|c| !c.is_alphabetic()) {
+            return Err(GameError::UnsupportedLetter { letter });
+        }
+        if length > Pattern::MAX_LEN {
+            return Err(GameError::UnsupportedLength { len: length });
+        }
+        if !words.contains(answer) {
+            return Err(GameError::AnswerNotInList { answer: answer.to_string() });
         }
 
-        Self {
+        Ok(Self {
             words,
             answer: answer.to_string(),
-            lives: answer.len(),
-            guesses: [LetterScore::Unknown; 26]
+            lives: length,
+            hard: false,
+            tries: Vec::new(),
+            knowledge: Knowledge::new(length),
+            history: vec![Event::Created {
+                answer: answer.to_string(),
+                attempts: length,
+                hard: false,
+                seed: None
+            }]
+        })
+    }
+
+    /// Starts a game with an answer picked by `picker`
+    pub fn new(words: Dictionary, picker: &AnswerPicker) -> Result<Self, GameError> {
+        // Without a seed, a random one is recorded so that the game can be played again
+        let seed = picker.seed.unwrap_or_else(|| StdRng::from_entropy().gen());
+        let mut rng = StdRng::seed_from_u64(seed);
+        let answer = pick_answer(picker.answers.as_ref().unwrap_or(&words), picker, &mut rng)
+            .ok_or(GameError::NoAnswer)?
+            .to_string();
+
+        let mut game = Self::new_with_answer(words, &answer)?;
+        if let Some(Event::Created { seed: created_seed, .. }) = game.history.first_mut() {
+            *created_seed = Some(seed);
         }
+        Ok(game)
     }
 
-    pub fn new(words: Vec<String>) -> Self {
-        let answer: String =
-            if let Some(value) = words.choose(&mut rand::thread_rng()) {
-                value.clone()
-            }
-            else {
-                String::from("demo")
+    /// Rebuilds a game by replaying `events`, checking that every event gives the recorded result
+    pub fn from_history(words: Dictionary, events: &[Event]) -> Result<Self, HistoryError> {
+        let mut game = match events.first() {
+            Some(Event::Created { answer, attempts, hard, seed }) => {
+                let mut game = Self::new_with_answer(words, answer)?
+                    .with_config(GameConfig { attempts: Some(*attempts), hard: *hard })?;
+                game.history[0] = Event::Created {
+                    answer: answer.clone(),
+                    attempts: *attempts,
+                    hard: *hard,
+                    seed: *seed
+                };
+                game
+            },
+            _ =>
+                return Err(HistoryError::MisplacedCreation)
+        };
+
+        for event in &events[1..] {
+            let replayed = match event {
+                Event::Created { .. } =>
+                    return Err(HistoryError::MisplacedCreation),
+                Event::Guessed { word, pattern } =>
+                    game.guess(word)? == *pattern,
+                Event::HintUsed { letter, pos } =>
+                    game.hint()? == (*letter, *pos),
+                // Recorded by the last guess
+                Event::Ended { won } =>
+                    game.history.last() == Some(&Event::Ended { won: *won })
             };
-        Self::new_with_answer(words, &answer)
+            if !replayed {
+                return Err(HistoryError::Mismatch { event: event.clone() });
+            }
+        }
+        Ok(game)
+    }
+
+    /// Rebuilds a game from its record, refusing it if it was played with other words than `words`
+    pub fn from_record(words: Dictionary, record: &Record) -> Result<Self, HistoryError> {
+        if let Some(expected) = record.words {
+            let found = words.fingerprint();
+            if expected != found {
+                return Err(HistoryError::OtherWords { expected, found });
+            }
+        }
+        Self::from_history(words, &record.events)
+    }
+
+    /// Sets the number of attempts and hard mode, refused if there would be no attempt
+    pub fn with_config(mut self, config: GameConfig) -> Result<Self, GameError> {
+        if let Some(attempts) = config.attempts {
+            if attempts == 0 {
+                return Err(GameError::NoAttempts);
+            }
+            self.lives = attempts;
+        }
+        self.hard = config.hard;
+        if let Some(Event::Created { attempts, hard, .. }) = self.history.first_mut() {
+            *attempts = self.lives;
+            *hard = self.hard;
+        }
+        Ok(self)
     }
 
-    pub fn guess(&mut self, guess: &str) -> Result<Vec<LetterScore>, InvalidWord> {
-        if guess.len() != self.answer.len() {
-            Err(InvalidWord::DifferentLength)
+    /// Scores `guess` against the answer and uses a life, or tells why the guess is refused
+    pub fn guess(&mut self, guess: &str) -> Result<Pattern, GameError> {
+        let guess = words::canonicalize(guess);
+        if self.is_over() {
+            Err(GameError::GameOver)
+        }
+        else if guess.chars().count() != self.answer.chars().count() {
+            Err(InvalidWord::DifferentLength.into())
         }
-        else if !self.words.contains(&guess.to_string()) {
-            Err(InvalidWord::NotAWord)
+        else if !self.words.contains(&guess) {
+            Err(InvalidWord::NotAWord.into())
+        }
+        else if let (true, Some(missing)) = (self.hard, self.knowledge.missing_hint(&guess)) {
+            Err(missing.into())
         }
         else {
-            let mut answer = self.answer.clone();
-            // Initialize all wrong
-            let mut score: Vec<LetterScore> =
-                iter::repeat(LetterScore::Wrong)
-                .take(guess.len())
-                .collect();
-            // Find the letter that are correct
-            for i in 0..answer.len() {
-                let char_answer = answer.chars().nth(i).unwrap();
-                let char_guess = guess.chars().nth(i).unwrap();
-                // All wrong by default
-                self.set_guess_at_index(char_guess, LetterScore::Wrong);
-                if char_guess == char_answer {
-                    // Character matched, score and replace to not score again
-                    score[i] = LetterScore::Correct;
-                    answer = answer.replacen(char_guess, ":", 1);
-                    self.set_guess_at_index(char_guess, LetterScore::Correct);
-                }
-            }
-            // Find the letters that are present
-            for i in 0..answer.len() {
-                let char_guess = guess.chars().nth(i).unwrap();
-                if score[i] != LetterScore::Correct && answer.contains(char_guess) {
-                    // Character matched, score and replace to not score again
-                    score[i] = LetterScore::Present;
-                    answer = answer.replacen(char_guess, ":", 1);
-                    self.set_guess_at_index(char_guess, LetterScore::Present);
-                }
-            }
+            let score = pattern::score(&self.answer, &guess);
+            self.knowledge.add(&guess, score);
+            self.history.push(Event::Guessed { word: guess.clone(), pattern: score });
+            self.tries.push((guess, score));
             self.lives -= 1;
+            if self.is_over() {
+                self.history.push(Event::Ended { won: self.is_won() });
+            }
 
             Ok(score)
         }
     }
 
+    /// Reveals the letter of the answer at the first position no guess or hint found
+    pub fn hint(&mut self) -> Result<(char, usize), GameError> {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        let (pos, letter) = self.answer
+            .chars()
+            .enumerate()
+            .find(|(pos, letter)| !self.knowledge.letter(*letter).is_correct_at(*pos))
+            .ok_or(GameError::NoHintLeft)?;
+
+        self.knowledge.reveal(letter, pos);
+        self.history.push(Event::HintUsed { letter, pos });
+        Ok((letter, pos))
+    }
+
+    /// Takes back the last guess or hint, `false` if there is none or the game is over
+    pub fn undo(&mut self) -> bool {
+        // The answer is revealed once the game is over
+        if self.is_over() {
+            return false;
+        }
+        let last = self.history
+            .iter()
+            .rposition(|event| matches!(event, Event::Guessed { .. } | Event::HintUsed { .. }));
+        match last {
+            Some(last) => {
+                // Replaying events recorded by this same game cannot fail
+                match Self::from_history(self.words.clone(), &self.history[..last]) {
+                    Ok(game) => {
+                        *self = game;
+                        true
+                    },
+                    Err(_) => false
+                }
+            },
+            None => false
+        }
+    }
+
+    /// Every event of the game so far, starting with its creation
+    pub fn history(&self) -> &[Event] {
+        &self.history
+    }
+
+    /// Scores of a row without any guess, one per letter of the answer
     pub fn guess_empty(&self) -> Vec<LetterScore> {
-        iter::repeat(LetterScore::Unknown)
-            .take(self.answer.len())
+        iter::repeat_n(LetterScore::Unknown, self.answer.chars().count())
             .collect()
     }
 
 
+    /// Best score each of `letters` got in the previous guesses
     pub fn known_guesses(&self, letters: &str) -> Vec<LetterScore> {
         letters
             .chars()
             .map(
                 |c|
-                self.guess_at_index(c).to_owned()
+                self.knowledge.letter(c).score()
             ).collect()
     }
 
-    fn guess_at_index(&self, char: char) -> LetterScore {
-        if !char.is_alphabetic() {
-            panic!("Character {char} is not alphabetical")
-        }
+    /// Everything the previous guesses tell about the answer
+    pub fn knowledge(&self) -> &Knowledge {
+        &self.knowledge
+    }
 
-        self.guesses[char as usize - 'a' as usize]
+    /// Guesses left
+    pub fn lives(&self) -> usize {
+        self.lives
+    }
+
+    /// Whether the last guess was the answer
+    pub fn is_won(&self) -> bool {
+        self.tries.last().is_some_and(|(_, pattern)| pattern.is_correct())
+    }
+
+    /// Whether no more guesses can be made, the game being won or lost
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.lives == 0
+    }
+
+    /// Word to guess
+    pub fn answer(&self) -> &str {
+        &self.answer
+    }
+
+    /// Words that can be guessed
+    pub fn words(&self) -> &Dictionary {
+        &self.words
+    }
+
+    /// Previous guesses with their scores, in order
+    pub fn tries(&self) -> &[(String, Pattern)] {
+        &self.tries
     }
-    fn set_guess_at_index(&mut self, char: char, score: LetterScore) {
-        if !char.is_alphabetic() {
-            panic!("Character {char} is not alphabetical")
-        }
 
-        if self.guess_at_index(char) < score {
-            self.guesses[char as usize - 'a' as usize] = score;
+    /// History of the game with the fingerprint of its words, to save it and rebuild it with [`Self::from_record`]
+    pub fn record(&self) -> Record {
+        Record {
+            words: Some(self.words.fingerprint()),
+            events: self.history.clone()
         }
     }
+}
 
-    pub fn lives(&self) -> usize {
-        self.lives
+
+/// Serialized as its [`Record`], read back as one and rebuilt with [`WordleGame::from_record`]
+#[cfg(feature = "serde")]
+impl serde::Serialize for WordleGame {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.record(), serializer)
+    }
+}
+
+
+
+fn pick_answer<'a, R>(words: &'a Dictionary, picker: &AnswerPicker, rng: &mut R) -> Option<&'a str>
+    where R: Rng
+{
+    let frequency = |word: &str| *picker.frequencies.get(word).unwrap_or(&0);
+    let mut candidates: Vec<&str> = words
+        .iter()
+        .filter(|word| !picker.blocklist.contains(word))
+        .collect();
+
+    let weights: Vec<f64> = match picker.difficulty {
+        Difficulty::Normal =>
+            return candidates.choose(rng).copied(),
+        Difficulty::Top(count) => {
+            candidates.sort_by_key(|word| std::cmp::Reverse(frequency(word)));
+            candidates.truncate(count.max(1));
+            return candidates.choose(rng).copied();
+        },
+        Difficulty::Easy =>
+            candidates.iter().map(|w| frequency(w) as f64).collect(),
+        Difficulty::Hard =>
+            candidates.iter().map(|w| 1.0 / (frequency(w) as f64 + 1.0)).collect()
+    };
+
+    // Without any frequencies there is nothing to weight by
+    match WeightedIndex::new(&weights) {
+        Ok(index) =>
+            candidates.get(index.sample(rng)).copied(),
+        Err(_) =>
+            candidates.choose(rng).copied()
     }
 }
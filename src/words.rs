@@ -1,21 +1,143 @@
+//! Reading, parsing and validating word lists.
+
+#![allow(clippy::result_large_err)]
+
 use std::{
+    collections::HashMap,
     fs,
     fmt::{
         Display
     },
-    ops::RangeInclusive
+    io::Read,
+    ops::RangeInclusive,
+    path::Path
 };
 
+use flate2::read::GzDecoder;
+use serde_json::Value;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::dictionary::{Dictionary, Normalization};
+
 
 
 type Words = Vec<String>;
 type WordErrors = Vec<WordError>;
 type InvalidWords = Vec<InvalidWord>;
+type Frequencies = HashMap<String, u64>;
+type Entries = Vec<Entry>;
+
+
+
+/// Fields of the leading `#! key: value` lines of a word list
+#[derive(
+    Debug, Default,
+    Clone,
+    PartialEq, Eq
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WordListHeader {
+    /// Name of the list
+    pub name: Option<String>,
+    /// Language of the words
+    pub language: Option<String>,
+    /// Length every word of the list must have
+    pub length: Option<usize>
+}
 
-#[derive(Debug)]
+/// A word as it is written in the file, before validation
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// Line in a text list or index in a JSON list
+    pub pos: usize,
+    /// Word as written
+    pub word: String,
+    /// Frequency as written after the word, if any
+    pub frequency: Option<String>
+}
+
+/// The valid words of a list, ready to play with
+#[derive(Debug, Default, Clone)]
+pub struct WordList {
+    /// Header of the list, or of the last merged list that has each field
+    pub header: WordListHeader,
+    /// Canonical words of the list
+    pub words: Dictionary,
+    /// Frequencies of the words that have one
+    pub frequencies: Frequencies,
+    /// What was changed in the words to make the dictionary
+    pub normalization: Normalization
+}
+
+impl WordList {
+    /// Adds the words of `other`, its frequencies and header fields take precedence
+    pub fn merge(&mut self, other: WordList) {
+        self.words = Dictionary::from(
+            self.words.iter()
+                .chain(other.words.iter())
+                .map(str::to_string)
+                .collect::<Words>()
+        );
+        self.frequencies.extend(other.frequencies);
+        self.normalization.changed.extend(other.normalization.changed);
+        self.normalization.duplicates.extend(other.normalization.duplicates);
+        self.header = WordListHeader {
+            name: other.header.name.or(self.header.name.take()),
+            language: other.header.language.or(self.header.language.take()),
+            length: other.header.length.or(self.header.length)
+        };
+    }
+}
+
+
+/// How the words of a list are written
+#[derive(
+    Debug,
+    Clone, Copy,
+    PartialEq, Eq
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum WordListFormat {
+    /// One word per line, optionally followed by a tab and a frequency.
+    /// Lines starting with `#` are comments, `#!` lines before the first word are the header.
+    Text,
+    /// An array of words, either as strings or as `{"word": ..., "frequency": ...}` objects.
+    Json
+}
+
+impl WordListFormat {
+    /// Format of the list at `path`, from its extension or else from its content
+    pub fn detect(path: &str, content: &str) -> Self {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("json") =>
+                Self::Json,
+            Some("txt" | "tsv") =>
+                Self::Text,
+            _ =>
+                if content.trim_start().starts_with('[') {
+                    Self::Json
+                }
+                else {
+                    Self::Text
+                }
+        }
+    }
+}
+
+/// A word of a list that failed validation
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InvalidWord {
+    /// Line in a text list or index in a JSON list
     pub pos: usize,
+    /// Word as written
     pub word: String,
+    /// Every reason the word is invalid
     pub errors: WordErrors
 }
 
@@ -38,11 +160,23 @@ impl Display for InvalidWord {
 }
 
 
-#[derive(Debug)]
+/// Why a word list cannot be used entirely
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum WordListError {
+    /// The file does not exist or cannot be read
     NoFile,
+    /// There are no words in the list
     Empty,
+    /// The content is not a text or JSON word list, or not valid UTF-8
+    InvalidFormat {
+        /// Error of the format
+        reason: String
+    },
+    /// Some words are invalid, the other words of the list are still usable
     InvalidWords {
+        /// Invalid words in the order of the list
         words: InvalidWords
     }
 }
@@ -52,6 +186,7 @@ impl Display for WordListError {
         match self {
             Self::NoFile => write!(f, "File cannot be read/does not exist"),
             Self::Empty => write!(f, "Word list format is improperly formatted"),
+            Self::InvalidFormat { reason } => write!(f, "Word list cannot be parsed: {reason}"),
             Self::InvalidWords { words } => {
                 let errors = words
                     .iter()
@@ -66,15 +201,35 @@ impl Display for WordListError {
 
 
 
-#[derive(Debug)]
+/// Why a single word is invalid
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum WordError {
+    /// The word has a character that is not a letter
     InvalidCharacter {
+        /// Position of the character in the word
         pos: usize,
+        /// Invalid character
         char: char
     },
+    /// The word is shorter or longer than any answer can be
     InvalidLength {
+        /// Number of letters of the word
         len: usize
     },
+    /// The word does not have the length the header requires
+    UnexpectedLength {
+        /// Number of letters of the word
+        len: usize,
+        /// Length of the header
+        expected: usize
+    },
+    /// The frequency after the word is not a positive integer
+    InvalidFrequency {
+        /// Frequency as written
+        value: String
+    }
 }
 
 impl Display for WordError {
@@ -85,41 +240,320 @@ impl Display for WordError {
                 f, "Length of {len}, it should be between {} and {}",
                 WORD_RANGE.min().unwrap(),
                 WORD_RANGE.max().unwrap()
-            )
+            ),
+            WordError::UnexpectedLength {len, expected} => write!(f, "Length of {len}, the header requires {expected}"),
+            WordError::InvalidFrequency {value} => write!(f, "Invalid frequency '{value}', it should be a positive integer")
+        }
+    }
+}
+
+
+
+/// The word list bundled with the game
+pub fn default_words() -> Dictionary {
+    Dictionary::bundled()
+}
+
+/// Reads and merges the word lists in order, or uses the default words if there are none.
+/// Words of the exclusion lists are removed from the result.
+/// Errors are returned with the path of the file they come from, words without errors are still kept.
+pub fn read_all<S>(paths: &[S], excludes: &[S]) -> (WordList, Vec<(String, WordListError)>)
+    where S: AsRef<str>
+{
+    let mut errors = Vec::new();
+    let mut list = WordList::default();
+    if paths.is_empty() {
+        list.words = default_words();
+    }
+    for path in paths {
+        match read_from(path.as_ref()) {
+            Ok(other) =>
+                list.merge(other),
+            Err((other, error)) => {
+                list.merge(other);
+                errors.push((path.as_ref().to_string(), error));
+            }
         }
     }
+
+    for path in excludes {
+        match read_blocklist(path.as_ref()) {
+            Ok(excluded) =>
+                list.words = list.words.without(&excluded),
+            Err(error) =>
+                errors.push((path.as_ref().to_string(), error))
+        }
+    }
+
+    (list, errors)
 }
 
+/// Words never picked as the answer, bundled with the game
+pub fn default_blocklist() -> Dictionary {
+    match parse_entries(include_str!("../res/blocklist.txt"), WordListFormat::Text) {
+        Ok((_, entries)) =>
+            Dictionary::from(entries.into_iter().map(|e| e.word).collect::<Words>()),
+        Err(_) =>
+            Dictionary::default()
+    }
+}
+
+/// Reads a list of words to exclude.
+/// Words are only canonicalized and not validated, since excluding a word that cannot be played is harmless.
+pub fn read_blocklist(path: &str) -> Result<Dictionary, WordListError> {
+    let (_, entries) = read_entries(path)?;
+    Ok(Dictionary::from(entries.into_iter().map(|e| e.word).collect::<Words>()))
+}
+
+/// Form under which words are stored and compared:
+/// without trailing whitespace, composed (NFC) and lowercase
+pub fn canonicalize(word: &str) -> String {
+    word.trim_end().nfc().collect::<String>().to_lowercase()
+}
+
+/// Reads the word list at `path`, in any format and compressed or not.
+/// When some words are invalid, the list of the valid ones comes with the error
+pub fn read_from(path: &str) -> Result<WordList, (WordList, WordListError)> {
+    match read_content(path) {
+        Ok((content, format)) =>
+            parse(&content, format),
+        Err(error) =>
+            Err((WordList::default(), error))
+    }
+}
 
+/// Parses a word list in `format`, like [`read_from`] does
+pub fn parse(content: &str, format: WordListFormat) -> Result<WordList, (WordList, WordListError)> {
+    match parse_entries(content, format) {
+        Ok((header, entries)) =>
+            from_entries(header, entries),
+        Err(error) =>
+            Err((WordList::default(), error))
+    }
+}
 
-pub fn default_words() -> Words {
-    include_str!("../res/word_list.txt").lines().map(str::to_owned).collect()
+/// Reads the words of the list at `path` as written, without validating them
+pub fn read_entries(path: &str) -> Result<(WordListHeader, Entries), WordListError> {
+    let (content, format) = read_content(path)?;
+    parse_entries(&content, format)
 }
 
-pub fn read_from(path: &str) -> Result<Words, (Words, WordListError)> {
-    let words: Words = match fs::read_to_string(path) {
+fn read_content(path: &str) -> Result<(String, WordListFormat), WordListError> {
+    let mut bytes = match fs::read(path) {
+        Ok(bytes) =>
+            bytes,
+        Err(_) =>
+            return Err(WordListError::NoFile)
+    };
+
+    let mut path = path;
+    if is_compressed(path, &bytes) {
+        let mut decompressed = Vec::new();
+        if let Err(error) = GzDecoder::new(&bytes[..]).read_to_end(&mut decompressed) {
+            return Err(WordListError::InvalidFormat { reason: format!("Cannot decompress gzip: {error}") });
+        }
+        bytes = decompressed;
+        path = path.strip_suffix(".gz").unwrap_or(path);
+    }
+
+    let content = match String::from_utf8(bytes) {
         Ok(content) =>
-            content.lines().map(str::to_owned).collect(),
+            content,
         Err(_) =>
-            return Err((vec![], WordListError::NoFile))
+            return Err(WordListError::InvalidFormat { reason: String::from("File is not valid UTF-8") })
+    };
+
+    let format = WordListFormat::detect(path, &content);
+    Ok((content, format))
+}
+
+/// Parses the words of a list in `format` as written, without validating them
+pub fn parse_entries(content: &str, format: WordListFormat) -> Result<(WordListHeader, Entries), WordListError> {
+    let parsed = match format {
+        WordListFormat::Text =>
+            parse_text(content),
+        WordListFormat::Json =>
+            parse_json(content)
     };
+    parsed.map_err(|reason| WordListError::InvalidFormat { reason })
+}
 
-    if words.len() == 0 {
-        Err((vec![], WordListError::Empty))
+/// Whether the file at `path` with these bytes is compressed with gzip
+pub fn is_compressed(path: &str, bytes: &[u8]) -> bool {
+    path.ends_with(".gz") || bytes.starts_with(&GZIP_MAGIC)
+}
+
+fn from_entries(header: WordListHeader, entries: Entries) -> Result<WordList, (WordList, WordListError)> {
+    if entries.is_empty() {
+        return Err((WordList { header, ..Default::default() }, WordListError::Empty));
+    }
+
+    let mut list = WordList {
+        header,
+        ..Default::default()
+    };
+    let mut words: Words = Vec::new();
+    let mut invalid: InvalidWords = Vec::new();
+    for entry in entries {
+        match validate_entry(&entry, &list.header) {
+            Ok((word, frequency)) => {
+                if let Some(frequency) = frequency {
+                    list.frequencies.entry(word).or_insert(frequency);
+                }
+                words.push(entry.word);
+            },
+            Err(errors) =>
+                invalid.push(InvalidWord {
+                    pos: entry.pos,
+                    word: entry.word,
+                    errors
+                })
+        }
+    }
+
+    (list.words, list.normalization) = Dictionary::new(words);
+
+    if !invalid.is_empty() {
+        Err((list, WordListError::InvalidWords { words: invalid }))
     }
     else {
-        match validate_list(&words) {
-            Ok(words) =>
-                Ok(words),
-            Err((words, invalid)) =>
-                Err((words, WordListError::InvalidWords { words: invalid }))
+        Ok(list)
+    }
+}
+
+
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+fn parse_text(content: &str) -> Result<(WordListHeader, Entries), String> {
+    let mut header = WordListHeader::default();
+    let mut entries = Vec::new();
+    for (pos, line) in content.lines().enumerate() {
+        if let Some(field) = line.strip_prefix("#!") {
+            if entries.is_empty() {
+                parse_header_field(&mut header, field)
+                    .map_err(|reason| format!("Line {pos}: {reason}"))?;
+            }
         }
+        else if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        else {
+            let mut columns = line.split('\t');
+            entries.push(Entry {
+                pos,
+                word: columns.next().unwrap_or_default().to_string(),
+                frequency: columns.next().map(str::to_string)
+            });
+        }
+    }
+
+    Ok((header, entries))
+}
+
+fn parse_header_field(header: &mut WordListHeader, field: &str) -> Result<(), String> {
+    let (key, value) = match field.split_once(':') {
+        Some((key, value)) =>
+            (key.trim().to_lowercase(), value.trim().to_string()),
+        None =>
+            return Err(format!("Header field '{}' should be written as 'key: value'", field.trim()))
+    };
+    match key.as_str() {
+        "name" =>
+            header.name = Some(value),
+        "language" =>
+            header.language = Some(value),
+        "length" =>
+            header.length = match value.parse() {
+                Ok(length) =>
+                    Some(length),
+                Err(_) =>
+                    return Err(format!("Header length '{value}' is not a number"))
+            },
+        // Unknown fields are skipped so lists can carry extra metadata
+        _ => ()
+    }
+    Ok(())
+}
+
+fn parse_json(content: &str) -> Result<(WordListHeader, Entries), String> {
+    let values = match serde_json::from_str::<Value>(content) {
+        Ok(Value::Array(values)) =>
+            values,
+        Ok(_) =>
+            return Err(String::from("JSON word list should be an array")),
+        Err(error) =>
+            return Err(format!("Invalid JSON: {error}"))
+    };
+
+    let mut entries = Vec::new();
+    for (pos, value) in values.into_iter().enumerate() {
+        let entry = match value {
+            Value::String(word) =>
+                Entry { pos, word, frequency: None },
+            Value::Object(mut object) =>
+                match object.remove("word") {
+                    Some(Value::String(word)) =>
+                        Entry {
+                            pos,
+                            word,
+                            frequency: object.remove("frequency").map(|f| f.to_string())
+                        },
+                    _ =>
+                        return Err(format!("Element at index {pos} has no 'word' string"))
+                },
+            _ =>
+                return Err(format!("Element at index {pos} is not a word"))
+        };
+        entries.push(entry);
+    }
+
+    Ok((WordListHeader::default(), entries))
+}
+
+fn validate_entry(entry: &Entry, header: &WordListHeader) -> Result<(String, Option<u64>), WordErrors> {
+    let word = canonicalize(&entry.word);
+    let (word, mut errors) = match validate_word(&word) {
+        Ok(word) =>
+            (word, Vec::new()),
+        Err(errors) =>
+            (word, errors)
+    };
+
+    if let Some(expected) = header.length {
+        let len = word.chars().count();
+        if len != expected {
+            errors.push(WordError::UnexpectedLength { len, expected });
+        }
+    }
+    let frequency = match &entry.frequency {
+        Some(value) =>
+            match value.trim().parse() {
+                Ok(frequency) =>
+                    Some(frequency),
+                Err(_) => {
+                    errors.push(WordError::InvalidFrequency { value: value.clone() });
+                    None
+                }
+            },
+        None =>
+            None
+    };
+
+    if !errors.is_empty() {
+        Err(errors)
+    }
+    else {
+        Ok((word, frequency))
     }
 }
 
+
+
 const WORD_RANGE: RangeInclusive<usize> = 4..=6;
 
 
+/// Validates every word, giving the valid words lowercased and the invalid ones with their errors
 pub fn validate_list<S>(words: &[S]) -> Result<Words, (Words, InvalidWords)>
     where S: AsRef<str>
 {
@@ -138,7 +572,7 @@ pub fn validate_list<S>(words: &[S]) -> Result<Words, (Words, InvalidWords)>
         }
     }
 
-    if invalid.len() > 0 {
+    if !invalid.is_empty() {
         Err((valid, invalid))
     }
     else {
@@ -148,14 +582,16 @@ pub fn validate_list<S>(words: &[S]) -> Result<Words, (Words, InvalidWords)>
 
 
 
+/// The word lowercased if it has a valid length and only letters, else every reason it is invalid
 pub fn validate_word(word: &str) -> Result<String, WordErrors> {
     let word = word.to_lowercase();
 
     let mut errors: WordErrors = Vec::new();
 
-    if !WORD_RANGE.contains(&word.len()) {
+    let len = word.chars().count();
+    if !WORD_RANGE.contains(&len) {
         errors.push(WordError::InvalidLength{
-            len: word.len()
+            len
         });
     }
     for (pos, char) in word.chars().enumerate() {
@@ -166,7 +602,7 @@ pub fn validate_word(word: &str) -> Result<String, WordErrors> {
         }
     }
 
-    if errors.len() > 0 {
+    if !errors.is_empty() {
         Err(errors)
     }
     else {